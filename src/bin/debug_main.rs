@@ -1,15 +1,26 @@
 use clap::Parser;
-use mine_rs::{field::Field, Minesweeper, MinesweeperAction, MinesweeperStatus};
+use mine_rs::{
+    field::{position::Position, Field},
+    rules::{FirstClickPolicy, GenerationMode, Rules},
+    Minesweeper, MinesweeperAction, MinesweeperStatus,
+};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     #[arg(short = 'H', long)]
-    height: Option<u8>,
+    height: Option<u16>,
     #[arg(short, long)]
-    width: Option<u8>,
+    width: Option<u16>,
     #[arg(short, long)]
-    mines: Option<u16>,
+    mines: Option<u32>,
+    #[arg(short, long)]
+    seed: Option<u64>,
+    #[arg(long)]
+    no_guess: bool,
+    /// One of `none`, `safe-cell`, `opening` or a radius of the safe area around the first opened cell.
+    #[arg(long)]
+    first_click: Option<FirstClickPolicy>,
     #[arg(short, long)]
     debug: bool,
 }
@@ -21,9 +32,21 @@ fn main() {
         args.height.unwrap_or(5),
         args.width.unwrap_or(5),
         args.mines.unwrap_or(5),
+        Rules {
+            generation: if args.no_guess {
+                GenerationMode::NoGuess
+            } else {
+                GenerationMode::Random
+            },
+            first_click: args.first_click.unwrap_or_default(),
+            ..Default::default()
+        },
+        args.seed,
     )
     .expect("Couldn't create a game instance!");
 
+    println!("SEED: {}", game.get_seed());
+
     print_field(game.get_field(), args.debug);
 
     loop {
@@ -58,7 +81,7 @@ fn print_field(field: &Field, debug: bool) {
 
 fn get_action() -> Option<MinesweeperAction> {
     println!("Enter the desired action and the target cell's coordinates (e.g. `f 3,5` to flag the 6th cell on the 4th\
-    line. Other actions include `o` to open a cell and `s` to open the cell's surrounding cells):");
+    line. Other actions include `o` to open a cell, `s` to open the cell's surrounding cells and `a` to flag them):");
 
     let mut input = String::new();
     std::io::stdin().read_line(&mut input).ok()?;
@@ -74,13 +97,14 @@ fn get_action() -> Option<MinesweeperAction> {
         .trim()
         .split(',')
         .filter_map(|s| s.trim().parse().ok())
-        .collect::<Vec<u8>>();
+        .collect::<Vec<u16>>();
 
-    let cell_position = (*cell_position.first()?, *cell_position.get(1)?);
+    let cell_position = Position::new(*cell_position.first()?, *cell_position.get(1)?);
 
     match action {
         "o" => Some(MinesweeperAction::OpenCell(cell_position)),
         "s" => Some(MinesweeperAction::OpenSurroundingCells(cell_position)),
+        "a" => Some(MinesweeperAction::FlagSurroundingCells(cell_position)),
         "f" => Some(MinesweeperAction::FlagCell(cell_position)),
         _ => None,
     }
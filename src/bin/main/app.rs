@@ -1,19 +1,38 @@
 //! The terminal application
 
-use crate::app::MenuItem::{ColumnsAmount, MinesAmount, RowsAmount};
-use crate::app::MoveCursorDirection::{Down, Left, Right, Up};
+use crate::app::MenuItem::{
+    AntiMinesAmount, AutoFlag, ColumnsAmount, Continue, FirstClick, Generation, Grid as GridItem,
+    LivesAmount, MaxMinesPerCell, MinesAmount, Neighborhood as NeighborhoodItem, Preset,
+    QuestionMarks, Records, RowsAmount, Statistics, Topology as TopologyItem, Undo,
+};
+use crate::app::MoveCursorDirection::{
+    Down, DownLeft, DownRight, Left, Right, Up, UpLeft, UpRight,
+};
 use crate::game_ui::render_game;
 use crate::menu_ui::render_menu;
+use crate::player_stats::{self, PlayerStats};
+use crate::player_stats_ui::render_player_stats;
+use crate::records::{self, BoardConfig, Record};
+use crate::records_ui::render_records;
+use crate::save::{self, SavedGame};
 use crate::tui::Render;
 use crate::update::{ControlsSupport, MoveCursorDirection};
 pub use mine_rs::Minesweeper;
-use mine_rs::{MinesweeperAction, MinesweeperError, MinesweeperStatus};
+use mine_rs::{
+    difficulty::Difficulty,
+    field::position::Position,
+    rules::{
+        FirstClickPolicy, GenerationMode, Grid, Neighborhood, Rules, Topology, UndoPolicy,
+        MAX_MINES_PER_CELL,
+    },
+    solver::probability::{calculate_mine_probabilities, ProbabilityGrid},
+    MinesweeperAction, MinesweeperError, MinesweeperStatus,
+};
 use ratatui::Frame;
+use serde::{Deserialize, Serialize};
 use std::cmp;
 
-const DEFAULT_ROWS_AMOUNT: u8 = 16;
-const DEFAULT_COLUMNS_AMOUNT: u8 = 16;
-const DEFAULT_MINES_AMOUNT: u16 = 40;
+const DEFAULT_DIFFICULTY: Difficulty = Difficulty::Intermediate;
 
 /// The terminal application
 #[derive(Debug)]
@@ -26,25 +45,51 @@ pub struct App {
 
 impl App {
     pub fn new(
-        rows_amount: Option<u8>,
-        columns_amount: Option<u8>,
-        mines_amount: Option<u16>,
+        rows_amount: Option<u16>,
+        columns_amount: Option<u16>,
+        mines_amount: Option<u32>,
+        rules: Rules,
+        seed: Option<u64>,
     ) -> Result<App, MinesweeperError> {
         Ok(App {
             variant: if let (Some(rows_amount), Some(columns_amount), Some(mines_amount)) =
                 (rows_amount, columns_amount, mines_amount)
             {
-                AppVariant::InGame(AppGame::new(rows_amount, columns_amount, mines_amount)?)
+                AppVariant::InGame(Box::new(AppGame::new(GameConfig {
+                    rows_amount,
+                    columns_amount,
+                    mines_amount,
+                    rules,
+                    seed,
+                })?))
             } else {
-                AppVariant::InMenu(AppMenu::new(rows_amount, columns_amount, mines_amount))
+                AppVariant::InMenu(AppMenu::new(
+                    rows_amount,
+                    columns_amount,
+                    mines_amount,
+                    rules,
+                    seed,
+                ))
             },
             should_quit: false,
         })
     }
 
+    /// Creates the app with a previously saved game going on.
+    pub fn from_saved_game(saved_game: SavedGame) -> App {
+        App {
+            variant: AppVariant::InGame(Box::new(AppGame::from(saved_game))),
+            should_quit: false,
+        }
+    }
+
     pub fn tick(&mut self) {
         match &self.variant {
             AppVariant::InMenu(menu) if menu.should_quit => self.quit(),
+            AppVariant::InRecords(records) if records.should_leave => self.back_to_menu(),
+            AppVariant::InPlayerStats(player_stats) if player_stats.should_leave => {
+                self.back_to_menu()
+            }
             AppVariant::InGame(game) => {
                 if game.should_leave {
                     self.back_to_menu()
@@ -57,13 +102,24 @@ impl App {
     }
 
     pub fn back_to_menu(&mut self) {
-        if let AppVariant::InGame(game) = &self.variant {
-            let (rows_amount, columns_amount, _) = game.game.get_field().get_size();
-            self.variant = AppVariant::InMenu(AppMenu::new(
-                Some(rows_amount),
-                Some(columns_amount),
-                Some(game.game.get_field().get_mines_amount()),
-            ))
+        match &self.variant {
+            AppVariant::InGame(game) => {
+                self.variant = AppVariant::InMenu(AppMenu::from(game.config))
+            }
+            AppVariant::InRecords(records) => {
+                // return to the very menu item the records have been opened from
+                let mut menu = AppMenu::from(records.menu_config);
+                menu.selected_item = Records;
+
+                self.variant = AppVariant::InMenu(menu);
+            }
+            AppVariant::InPlayerStats(player_stats) => {
+                let mut menu = AppMenu::from(player_stats.menu_config);
+                menu.selected_item = Statistics;
+
+                self.variant = AppVariant::InMenu(menu);
+            }
+            AppVariant::InMenu(_) => (),
         };
     }
 
@@ -86,6 +142,22 @@ impl ControlsSupport for App {
         self.variant.perform_secondary_action()
     }
 
+    fn undo(&mut self) -> Result<(), MinesweeperError> {
+        self.variant.undo()
+    }
+
+    fn redo(&mut self) -> Result<(), MinesweeperError> {
+        self.variant.redo()
+    }
+
+    fn hint(&mut self) -> Result<(), MinesweeperError> {
+        self.variant.hint()
+    }
+
+    fn toggle_training_mode(&mut self) {
+        self.variant.toggle_training_mode();
+    }
+
     fn pause(&mut self) {
         self.variant.pause();
     }
@@ -103,11 +175,16 @@ impl Render for App {
 
 /// The current application variant.
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum AppVariant {
     /// When the menu's being displayed
     InMenu(AppMenu),
     /// When the game's being displayed
-    InGame(AppGame),
+    InGame(Box<AppGame>),
+    /// When the records are being displayed
+    InRecords(AppRecords),
+    /// When the lifetime statistics are being displayed
+    InPlayerStats(AppPlayerStats),
 }
 
 impl ControlsSupport for AppVariant {
@@ -115,31 +192,51 @@ impl ControlsSupport for AppVariant {
         match self {
             AppVariant::InMenu(menu) => menu.move_cursor(direction),
             AppVariant::InGame(game) => game.move_cursor(direction),
+            AppVariant::InRecords(records) => records.move_cursor(direction),
+            // everything's displayed at once, so there's nothing to move between
+            AppVariant::InPlayerStats(_) => (),
         }
     }
 
     fn perform_main_action(&mut self) -> Result<(), MinesweeperError> {
         match self {
+            AppVariant::InMenu(menu) if menu.selected_item == Records => {
+                *self = AppVariant::InRecords(AppRecords::new(menu.get_config()));
+            }
+            AppVariant::InMenu(menu) if menu.selected_item == Statistics => {
+                *self = AppVariant::InPlayerStats(AppPlayerStats::new(menu.get_config()));
+            }
             AppVariant::InMenu(menu) => {
-                let game = menu.create_new_game();
-
-                if game.is_ok() {
-                    *self = AppVariant::InGame(game.unwrap());
+                let game = if menu.selected_item == Continue {
+                    save::take_default()
+                        .map(AppGame::from)
+                        .map_err(|error| format!("Couldn't load the saved game: {error}"))
                 } else {
-                    menu.error = game.err();
+                    menu.create_new_game().map_err(|error| format!("{error:?}"))
+                };
+
+                match game {
+                    Ok(game) => *self = AppVariant::InGame(Box::new(game)),
+                    Err(error) => menu.error = Some(error),
                 }
             }
             AppVariant::InGame(game) => {
-                let result = game.open_cell_or_surrounding_cells_or_confirm_leave()?;
-
-                if let Some((rows_amount, columns_amount, mines_amount)) = result {
-                    *self = AppVariant::InGame(AppGame::new(
-                        rows_amount,
-                        columns_amount,
-                        mines_amount,
-                    )?);
+                match game.open_cell_or_surrounding_cells_or_confirm_leave() {
+                    Ok(Some(config)) => *self = AppVariant::InGame(Box::new(AppGame::new(config)?)),
+                    Ok(None) => (),
+                    // the game couldn't be started (e.g., no suitable field layout has been found), so go back to
+                    // the menu and show the error there
+                    Err(MinesweeperError::FieldError(error)) => {
+                        let mut menu = AppMenu::from(game.config);
+                        menu.error = Some(format!("{error:?}"));
+
+                        *self = AppVariant::InMenu(menu);
+                    }
+                    Err(error) => return Err(error),
                 }
             }
+            // there's nothing to choose on the records and the statistics screens
+            AppVariant::InRecords(_) | AppVariant::InPlayerStats(_) => (),
         }
 
         Ok(())
@@ -148,12 +245,44 @@ impl ControlsSupport for AppVariant {
     fn perform_secondary_action(&mut self) -> Result<(), MinesweeperError> {
         match self {
             AppVariant::InMenu(menu) => menu.restore_default(),
-            AppVariant::InGame(game) => game.toggle_flag()?,
+            AppVariant::InGame(game) => game.toggle_flag_or_save_and_leave()?,
+            AppVariant::InRecords(_) | AppVariant::InPlayerStats(_) => (),
+        }
+
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), MinesweeperError> {
+        // there's nothing to undo in the menu
+        if let AppVariant::InGame(game) = self {
+            game.undo()?;
+        }
+
+        Ok(())
+    }
+
+    fn redo(&mut self) -> Result<(), MinesweeperError> {
+        if let AppVariant::InGame(game) = self {
+            game.redo()?;
+        }
+
+        Ok(())
+    }
+
+    fn hint(&mut self) -> Result<(), MinesweeperError> {
+        if let AppVariant::InGame(game) = self {
+            game.hint()?;
         }
 
         Ok(())
     }
 
+    fn toggle_training_mode(&mut self) {
+        if let AppVariant::InGame(game) = self {
+            game.toggle_training_mode();
+        }
+    }
+
     fn pause(&mut self) {
         // it's only possible to toggle the pause for the game, not for the menu
         if let AppVariant::InGame(game) = self {
@@ -176,6 +305,12 @@ impl ControlsSupport for AppVariant {
                     game.confirm_or_cancel_leave_or_leave();
                 }
             }
+            AppVariant::InRecords(records) => {
+                records.leave();
+            }
+            AppVariant::InPlayerStats(player_stats) => {
+                player_stats.leave();
+            }
         }
     }
 }
@@ -185,6 +320,10 @@ impl Render for AppVariant {
         match self {
             AppVariant::InMenu(ref mut menu) => render_menu(menu, frame),
             AppVariant::InGame(ref mut game) => render_game(game, frame),
+            AppVariant::InRecords(ref mut records) => render_records(records, frame),
+            AppVariant::InPlayerStats(ref mut player_stats) => {
+                render_player_stats(player_stats, frame)
+            }
         }
     }
 }
@@ -192,35 +331,94 @@ impl Render for AppVariant {
 /// The Menu app.rs variant
 #[derive(Debug)]
 pub struct AppMenu {
-    pub rows_amount: u8,
-    pub columns_amount: u8,
-    pub mines_amount: u16,
+    pub rows_amount: u16,
+    pub columns_amount: u16,
+    pub mines_amount: u32,
+    pub rules: Rules,
     pub selected_item: MenuItem,
-    pub error: Option<MinesweeperError>,
+    /// Whether there's a game saved earlier that can be continued.
+    pub has_saved_game: bool,
+    pub error: Option<String>,
+    /// The seed requested by the player (if any). It's used for all the games started from the menu.
+    seed: Option<u64>,
     should_quit: bool,
 }
 
 #[derive(Debug, PartialEq, Clone, Eq)]
 pub enum MenuItem {
+    Continue,
+    Preset,
     ColumnsAmount,
     RowsAmount,
     MinesAmount,
+    Generation,
+    FirstClick,
+    Undo,
+    Topology,
+    Grid,
+    Neighborhood,
+    MaxMinesPerCell,
+    AntiMinesAmount,
+    QuestionMarks,
+    AutoFlag,
+    LivesAmount,
+    Records,
+    Statistics,
 }
 
 impl AppMenu {
-    fn new(rows_amount: Option<u8>, columns_amount: Option<u8>, mines_amount: Option<u16>) -> Self {
+    fn new(
+        rows_amount: Option<u16>,
+        columns_amount: Option<u16>,
+        mines_amount: Option<u32>,
+        rules: Rules,
+        seed: Option<u64>,
+    ) -> Self {
+        let has_saved_game = save::check_saved_game_exists();
+        let (default_rows_amount, default_columns_amount, default_mines_amount) =
+            DEFAULT_DIFFICULTY.get_size();
+
         AppMenu {
-            rows_amount: rows_amount.unwrap_or(DEFAULT_ROWS_AMOUNT),
-            columns_amount: columns_amount.unwrap_or(DEFAULT_COLUMNS_AMOUNT),
-            mines_amount: mines_amount.unwrap_or(DEFAULT_MINES_AMOUNT),
-            selected_item: ColumnsAmount,
+            rows_amount: rows_amount.unwrap_or(default_rows_amount),
+            columns_amount: columns_amount.unwrap_or(default_columns_amount),
+            mines_amount: mines_amount.unwrap_or(default_mines_amount),
+            rules,
+            // offer to continue the saved game first, if there's one
+            selected_item: if has_saved_game { Continue } else { Preset },
+            has_saved_game,
             error: None,
+            seed,
             should_quit: false,
         }
     }
 
     fn move_cursor(&mut self, direction: MoveCursorDirection) {
-        let layout = [ColumnsAmount, RowsAmount, MinesAmount];
+        let layout = [
+            Continue,
+            Preset,
+            ColumnsAmount,
+            RowsAmount,
+            MinesAmount,
+            Generation,
+            FirstClick,
+            Undo,
+            TopologyItem,
+            GridItem,
+            NeighborhoodItem,
+            MaxMinesPerCell,
+            AntiMinesAmount,
+            QuestionMarks,
+            AutoFlag,
+            LivesAmount,
+            Records,
+            Statistics,
+        ];
+        // the first item is only available when there's a saved game
+        let layout = if self.has_saved_game {
+            &layout[..]
+        } else {
+            &layout[1..]
+        };
 
         let mut current_index = layout
             .iter()
@@ -242,32 +440,217 @@ impl AppMenu {
             }
             Left => {
                 match self.selected_item {
+                    Continue => (),
+                    Preset => self.change_difficulty(false),
                     ColumnsAmount => self.columns_amount = self.columns_amount.saturating_sub(1),
                     RowsAmount => self.rows_amount = self.rows_amount.saturating_sub(1),
                     MinesAmount => self.mines_amount = self.mines_amount.saturating_sub(1),
+                    Generation => self.toggle_generation_mode(),
+                    FirstClick => self.change_first_click_policy(false),
+                    Undo => self.change_undo_policy(false),
+                    TopologyItem => self.toggle_topology(),
+                    GridItem => self.toggle_grid(),
+                    NeighborhoodItem => self.change_neighborhood(false),
+                    MaxMinesPerCell => self.change_max_mines_per_cell(false),
+                    AntiMinesAmount => {
+                        self.rules.anti_mines_amount =
+                            self.rules.anti_mines_amount.saturating_sub(1)
+                    }
+                    QuestionMarks => self.rules.question_marks = !self.rules.question_marks,
+                    AutoFlag => self.rules.auto_flag = !self.rules.auto_flag,
+                    LivesAmount => {
+                        self.rules.lives_amount =
+                            cmp::max(self.rules.lives_amount.saturating_sub(1), 1)
+                    }
+                    Records | Statistics => (),
                 };
             }
             Right => {
                 match self.selected_item {
+                    Continue => (),
+                    Preset => self.change_difficulty(true),
                     ColumnsAmount => self.columns_amount = self.columns_amount.saturating_add(1),
                     RowsAmount => self.rows_amount = self.rows_amount.saturating_add(1),
                     MinesAmount => self.mines_amount = self.mines_amount.saturating_add(1),
+                    Generation => self.toggle_generation_mode(),
+                    FirstClick => self.change_first_click_policy(true),
+                    Undo => self.change_undo_policy(true),
+                    TopologyItem => self.toggle_topology(),
+                    GridItem => self.toggle_grid(),
+                    NeighborhoodItem => self.change_neighborhood(true),
+                    MaxMinesPerCell => self.change_max_mines_per_cell(true),
+                    AntiMinesAmount => {
+                        self.rules.anti_mines_amount =
+                            self.rules.anti_mines_amount.saturating_add(1)
+                    }
+                    QuestionMarks => self.rules.question_marks = !self.rules.question_marks,
+                    AutoFlag => self.rules.auto_flag = !self.rules.auto_flag,
+                    LivesAmount => {
+                        self.rules.lives_amount = self.rules.lives_amount.saturating_add(1)
+                    }
+                    Records | Statistics => (),
                 };
             }
+            UpLeft | UpRight | DownLeft | DownRight => (),
         };
 
         self.selected_item = layout.get(current_index).unwrap().clone();
     }
 
+    /// Returns the difficulty the menu is set up for. It's one of the presets whenever the size matches one.
+    pub fn get_difficulty(&self) -> Difficulty {
+        Difficulty::from_size(self.rows_amount, self.columns_amount, self.mines_amount)
+    }
+
+    /// Switches to the harder (if `harder` is `true`) or the easier preset. A custom difficulty is considered harder
+    /// than all the presets, so it's only possible to switch to the hardest preset from it.
+    fn change_difficulty(&mut self, harder: bool) {
+        let presets = Difficulty::PRESETS;
+        let index = presets
+            .iter()
+            .position(|preset| *preset == self.get_difficulty())
+            .unwrap_or(presets.len());
+
+        let difficulty = if harder {
+            presets[cmp::min(index + 1, presets.len() - 1)]
+        } else {
+            presets[index.saturating_sub(1)]
+        };
+        self.set_difficulty(difficulty);
+    }
+
+    /// Sets the size of the field to the one of the difficulty.
+    fn set_difficulty(&mut self, difficulty: Difficulty) {
+        (self.rows_amount, self.columns_amount, self.mines_amount) = difficulty.get_size();
+    }
+
+    /// Returns the configuration of the game the menu is set up for.
+    fn get_config(&self) -> GameConfig {
+        GameConfig {
+            rows_amount: self.rows_amount,
+            columns_amount: self.columns_amount,
+            mines_amount: self.mines_amount,
+            rules: self.rules,
+            seed: self.seed,
+        }
+    }
+
     fn create_new_game(&self) -> Result<AppGame, MinesweeperError> {
-        AppGame::new(self.rows_amount, self.columns_amount, self.mines_amount)
+        AppGame::new(self.get_config())
+    }
+
+    fn toggle_generation_mode(&mut self) {
+        self.rules.generation = match self.rules.generation {
+            GenerationMode::Random => GenerationMode::NoGuess,
+            GenerationMode::NoGuess => GenerationMode::Random,
+        };
+    }
+
+    fn toggle_topology(&mut self) {
+        self.rules.topology = match self.rules.topology {
+            Topology::Plane => Topology::Torus,
+            Topology::Torus => Topology::Plane,
+        };
+    }
+
+    fn toggle_grid(&mut self) {
+        self.rules.grid = match self.rules.grid {
+            Grid::Square => Grid::Hexagonal,
+            Grid::Hexagonal => Grid::Square,
+        };
+    }
+
+    /// Switches to the policy with a larger (if `increase` is `true`) or a smaller safe area.
+    fn change_first_click_policy(&mut self, increase: bool) {
+        // the policies are ordered by their safe areas' radii, with no protection at all going first
+        let radius = self
+            .rules
+            .first_click
+            .get_safe_radius()
+            .map_or(-1, |radius| radius as i16);
+
+        self.rules.first_click = match if increase { radius + 1 } else { radius - 1 } {
+            ..=-1 => FirstClickPolicy::NoProtection,
+            0 => FirstClickPolicy::SafeCell,
+            1 => FirstClickPolicy::Opening,
+            radius => FirstClickPolicy::SafeArea(cmp::min(radius, u8::MAX as i16) as u8),
+        };
+    }
+
+    /// Switches to the more (if `more_permissive` is `true`) or the less permissive undo policy.
+    fn change_undo_policy(&mut self, more_permissive: bool) {
+        let policies = [
+            UndoPolicy::Forbidden,
+            UndoPolicy::NotAfterLoss,
+            UndoPolicy::Unrestricted,
+        ];
+
+        let index = policies
+            .iter()
+            .position(|policy| *policy == self.rules.undo)
+            .unwrap();
+
+        self.rules.undo = if more_permissive {
+            policies[cmp::min(index + 1, policies.len() - 1)]
+        } else {
+            policies[index.saturating_sub(1)]
+        };
+    }
+
+    /// Switches to the next (if `forward` is `true`) or the previous neighbourhood.
+    fn change_neighborhood(&mut self, forward: bool) {
+        let neighborhoods = [
+            Neighborhood::Adjacent,
+            Neighborhood::Orthogonal,
+            Neighborhood::KnightMove,
+            Neighborhood::Extended,
+        ];
+
+        let index = neighborhoods
+            .iter()
+            .position(|neighborhood| *neighborhood == self.rules.neighborhood)
+            .unwrap();
+
+        self.rules.neighborhood = if forward {
+            neighborhoods[cmp::min(index + 1, neighborhoods.len() - 1)]
+        } else {
+            neighborhoods[index.saturating_sub(1)]
+        };
+    }
+
+    /// Allows for one more (if `increase` is `true`) or one less mine per cell, within the allowed range.
+    fn change_max_mines_per_cell(&mut self, increase: bool) {
+        let max_mines_per_cell = if increase {
+            self.rules.max_mines_per_cell.saturating_add(1)
+        } else {
+            self.rules.max_mines_per_cell.saturating_sub(1)
+        };
+
+        self.rules.max_mines_per_cell = max_mines_per_cell.clamp(1, MAX_MINES_PER_CELL);
     }
 
     fn restore_default(&mut self) {
+        let (default_rows_amount, default_columns_amount, default_mines_amount) =
+            DEFAULT_DIFFICULTY.get_size();
+
         match self.selected_item {
-            ColumnsAmount => self.columns_amount = DEFAULT_COLUMNS_AMOUNT,
-            RowsAmount => self.rows_amount = DEFAULT_ROWS_AMOUNT,
-            MinesAmount => self.mines_amount = DEFAULT_MINES_AMOUNT,
+            Continue => (),
+            Preset => self.set_difficulty(DEFAULT_DIFFICULTY),
+            ColumnsAmount => self.columns_amount = default_columns_amount,
+            RowsAmount => self.rows_amount = default_rows_amount,
+            MinesAmount => self.mines_amount = default_mines_amount,
+            Generation => self.rules.generation = GenerationMode::default(),
+            FirstClick => self.rules.first_click = FirstClickPolicy::default(),
+            Undo => self.rules.undo = UndoPolicy::default(),
+            TopologyItem => self.rules.topology = Topology::default(),
+            GridItem => self.rules.grid = Grid::default(),
+            NeighborhoodItem => self.rules.neighborhood = Neighborhood::default(),
+            MaxMinesPerCell => self.rules.max_mines_per_cell = Rules::default().max_mines_per_cell,
+            AntiMinesAmount => self.rules.anti_mines_amount = Rules::default().anti_mines_amount,
+            QuestionMarks => self.rules.question_marks = Rules::default().question_marks,
+            AutoFlag => self.rules.auto_flag = Rules::default().auto_flag,
+            LivesAmount => self.rules.lives_amount = Rules::default().lives_amount,
+            Records | Statistics => (),
         };
     }
 
@@ -276,6 +659,38 @@ impl AppMenu {
     }
 }
 
+impl From<GameConfig> for AppMenu {
+    /// Creates a menu pre-filled with the configuration of a game (e.g., when leaving the game back to the menu).
+    fn from(config: GameConfig) -> Self {
+        AppMenu::new(
+            Some(config.rows_amount),
+            Some(config.columns_amount),
+            Some(config.mines_amount),
+            config.rules,
+            config.seed,
+        )
+    }
+}
+
+/// Everything needed to create a new game.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GameConfig {
+    pub rows_amount: u16,
+    pub columns_amount: u16,
+    pub mines_amount: u32,
+    pub rules: Rules,
+    /// The seed requested by the player (if any). Unlike the game's own seed, it's [`None`] when the player didn't ask
+    /// for a particular one, so that the consecutive games don't repeat the same field.
+    pub seed: Option<u64>,
+}
+
+impl GameConfig {
+    /// Returns the difficulty of the game. It's one of the presets whenever the size matches one.
+    pub fn get_difficulty(&self) -> Difficulty {
+        Difficulty::from_size(self.rows_amount, self.columns_amount, self.mines_amount)
+    }
+}
+
 /// The Game app.rs variant
 #[derive(Debug)]
 pub struct AppGame {
@@ -283,10 +698,10 @@ pub struct AppGame {
     pub game: Minesweeper,
     /// The amount of rows that should be rendered in the field. Must always be less or equal to the total amount of
     /// rows.
-    pub visible_rows_amount: u8,
+    pub visible_rows_amount: u16,
     /// The amount of columns that should be rendered in the field. Must always be less or equal to the total amount of
     /// columns.
-    pub visible_columns_amount: u8,
+    pub visible_columns_amount: u16,
     /// The window is a sliding frame-view into the field. This is used when the field is too big to be displayed in the
     /// given container.
     ///
@@ -307,37 +722,56 @@ pub struct AppGame {
     ///    _______
     ///
     /// */
-    pub window_offset: (u8, u8),
+    pub window_offset: Position,
     /// The position of the currently selected cell relative to the whole field. Must be added to the `window_offset`
     /// in order to get the position of the currently selected cell relative to the window (the visible part of the
     /// field).
-    pub cursor_position: (u8, u8),
+    pub cursor_position: Position,
     /// Whether the cancel key was pressed and now the game's in the state of waiting for a confirmation from the user
     /// to leave back to the menu.
     pub awaiting_leave_confirmation: bool,
+    /// The probabilities of the closed cells to be mined. Only calculated in the training mode ([`None`] otherwise).
+    pub probabilities: Option<ProbabilityGrid>,
+    /// The reason the game couldn't be saved when leaving (if it couldn't).
+    pub save_error: Option<String>,
+    /// The (zero-based) place the victory has taken in the records, if it's got there.
+    pub record_place: Option<usize>,
+    /// The reason the outcome couldn't be added to the records or to the statistics (if it couldn't).
+    pub results_error: Option<String>,
     /// Whether the leave was confirmed and now it's allowed to go back to the menu.
     pub should_leave: bool,
     /// Whether the app.rs should urgently leave without asking for a confirmation
     pub should_emergency_leave: bool,
+    /// The configuration the game has been created with. It's reused when starting a new game.
+    config: GameConfig,
+    /// Whether the start of the game has already been counted in the statistics.
+    is_start_recorded: bool,
+    /// Whether the outcome has already been submitted to the records and to the statistics. Only the first outcome of
+    /// a game counts, even if it gets undone afterwards.
+    is_result_recorded: bool,
 }
 
 impl AppGame {
-    fn new(
-        rows_amount: u8,
-        columns_amount: u8,
-        mines_amount: u16,
-    ) -> Result<Self, MinesweeperError> {
-        let game = Minesweeper::new(rows_amount, columns_amount, mines_amount)?;
+    fn new(config: GameConfig) -> Result<Self, MinesweeperError> {
+        let game =
+            Minesweeper::with_difficulty(config.get_difficulty(), config.rules, config.seed)?;
 
         Ok(AppGame {
             game,
             visible_rows_amount: 0,
             visible_columns_amount: 0,
-            window_offset: (0, 0),
-            cursor_position: (0, 0),
+            window_offset: Position::default(),
+            cursor_position: Position::default(),
             awaiting_leave_confirmation: false,
+            probabilities: None,
+            save_error: None,
+            record_place: None,
+            results_error: None,
             should_leave: false,
             should_emergency_leave: false,
+            config,
+            is_start_recorded: false,
+            is_result_recorded: false,
         })
     }
 
@@ -347,43 +781,71 @@ impl AppGame {
             return;
         }
 
-        let (field_height, field_width, _) = self.game.get_field().get_size();
-        let (cy, cx) = self.cursor_position;
+        // The diagonal neighbours are one column apart on the square grid. On the hexagonal one, the rows above and
+        // below an even row are shifted to the right, so its diagonal neighbours are the cells with the same column
+        // index and the one to the left of them (and vice versa for an odd row).
+        let (left_column_offset, right_column_offset) = match self.game.get_rules().grid {
+            Grid::Square => (-1, 1),
+            Grid::Hexagonal if self.cursor_position.row_index.is_multiple_of(2) => (-1, 0),
+            Grid::Hexagonal => (0, 1),
+        };
 
-        self.cursor_position = match direction {
-            Up => (cy.saturating_sub(1), cx),
-            Left => (cy, cx.saturating_sub(1)),
-            Down => (cmp::min(cy + 1, field_height - 1), cx),
-            Right => (cy, cmp::min(cx + 1, field_width - 1)),
+        let (row_offset, column_offset) = match direction {
+            Up => (-1, 0),
+            Left => (0, -1),
+            Down => (1, 0),
+            Right => (0, 1),
+            UpLeft => (-1, left_column_offset),
+            UpRight => (-1, right_column_offset),
+            DownLeft => (1, left_column_offset),
+            DownRight => (1, right_column_offset),
         };
 
-        let (cy, cx) = self.cursor_position;
-        let (oy, ox) = self.window_offset;
+        // the cursor stays put at the edges of a plane, but wraps around the edges of a torus
+        if let Some(position) = self.game.get_field().get_shifted_position(
+            self.cursor_position,
+            row_offset,
+            column_offset,
+        ) {
+            self.jump_cursor(position);
+        }
+    }
 
-        self.window_offset = {
-            let new_oy = if cy > oy + self.visible_rows_amount - 2 {
-                cmp::min(oy + 1, field_height - self.visible_rows_amount)
-            } else if cy < oy + 1 {
-                oy.saturating_sub(1)
-            } else {
-                self.window_offset.0
-            };
+    /// Moves the cursor to the given cell, sliding the window so that the cell is visible (along with a cell of margin
+    /// around it, if possible).
+    fn jump_cursor(&mut self, position: Position) {
+        let (field_height, field_width, _) = self.game.get_field().get_size();
+        self.cursor_position = position;
 
-            let new_ox = if cx > ox + self.visible_columns_amount - 2 {
-                cmp::min(ox + 1, field_width - self.visible_columns_amount)
-            } else if cx < ox + 1 {
-                ox.saturating_sub(1)
-            } else {
-                self.window_offset.1
-            };
+        let fit_offset = |cursor: u16, offset: u16, visible_amount: u16, total_amount: u16| {
+            let min_offset = cursor.saturating_add(2).saturating_sub(visible_amount);
+            let max_offset = cmp::max(cursor.saturating_sub(1), min_offset);
 
-            (new_oy, new_ox)
-        }
+            cmp::min(
+                offset.clamp(min_offset, max_offset),
+                total_amount.saturating_sub(visible_amount),
+            )
+        };
+
+        self.window_offset = Position::new(
+            fit_offset(
+                position.row_index,
+                self.window_offset.row_index,
+                self.visible_rows_amount,
+                field_height,
+            ),
+            fit_offset(
+                position.column_index,
+                self.window_offset.column_index,
+                self.visible_columns_amount,
+                field_width,
+            ),
+        );
     }
 
     fn open_cell_or_surrounding_cells_or_confirm_leave(
         &mut self,
-    ) -> Result<Option<(u8, u8, u16)>, MinesweeperError> {
+    ) -> Result<Option<GameConfig>, MinesweeperError> {
         if self.awaiting_leave_confirmation {
             self.leave();
             return Ok(None);
@@ -391,23 +853,139 @@ impl AppGame {
 
         if let MinesweeperStatus::End(_) = self.game.get_status() {
             // if the game has ended, start a new one
-            let (h, w, _) = self.game.get_field().get_size();
-            return Ok(Some((h, w, self.game.get_field().get_mines_amount())));
+            return Ok(Some(self.config));
         } else {
             // otherwise, open a cell or surrounding cells
+            let open_cells_amount = self.game.get_field().get_open_cells_amount();
             self.game
                 .take_action(MinesweeperAction::OpenCellOrSurroundingCells(
                     self.cursor_position,
                 ))?;
+            self.refresh_probabilities(open_cells_amount);
+            self.record_progress();
         }
 
         Ok(None)
     }
 
+    fn toggle_flag_or_save_and_leave(&mut self) -> Result<(), MinesweeperError> {
+        if self.awaiting_leave_confirmation {
+            self.save_and_leave();
+            return Ok(());
+        }
+
+        self.toggle_flag()
+    }
+
+    fn undo(&mut self) -> Result<(), MinesweeperError> {
+        // the policy might not allow to undo at the moment, in which case the key is simply ignored
+        if !self.awaiting_leave_confirmation && self.game.can_undo() {
+            let open_cells_amount = self.game.get_field().get_open_cells_amount();
+            self.game.undo()?;
+            self.refresh_probabilities(open_cells_amount);
+        }
+
+        Ok(())
+    }
+
+    fn redo(&mut self) -> Result<(), MinesweeperError> {
+        if !self.awaiting_leave_confirmation && self.game.can_redo() {
+            let open_cells_amount = self.game.get_field().get_open_cells_amount();
+            self.game.redo()?;
+            self.refresh_probabilities(open_cells_amount);
+            self.record_progress();
+        }
+
+        Ok(())
+    }
+
+    /// Asks the game for a hint and moves the cursor to the suggested cell.
+    fn hint(&mut self) -> Result<(), MinesweeperError> {
+        if self.awaiting_leave_confirmation {
+            return Ok(());
+        }
+
+        if let MinesweeperStatus::Pre | MinesweeperStatus::On = self.game.get_status() {
+            self.game.take_action(MinesweeperAction::Hint)?;
+
+            if let Some(hint) = self.game.get_hint() {
+                self.jump_cursor(hint.position);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Shows or hides the probabilities of the closed cells to be mined.
+    fn toggle_training_mode(&mut self) {
+        self.probabilities = match self.probabilities {
+            Some(_) => None,
+            None => Some(calculate_mine_probabilities(self.game.get_field())),
+        };
+    }
+
+    /// Recalculates the probabilities (only in the training mode) if the number of the open cells differs from the one
+    /// before the action. The flags don't affect the probabilities, and an action can't both open and close cells, so
+    /// the calculation is skipped for the actions that haven't opened or closed any cells.
+    fn refresh_probabilities(&mut self, open_cells_amount_before: u32) {
+        if self.probabilities.is_some()
+            && self.game.get_field().get_open_cells_amount() != open_cells_amount_before
+        {
+            self.probabilities = Some(calculate_mine_probabilities(self.game.get_field()));
+        }
+    }
+
+    /// Counts the start and the outcome of the game in the statistics (and submits the victory to the records) as soon
+    /// as they happen. Must be called after every action that might change the game's status.
+    fn record_progress(&mut self) {
+        let board = BoardConfig::from(self.config);
+        let status = self.game.get_status().clone();
+
+        if !self.is_start_recorded && status != MinesweeperStatus::Pre {
+            if let Err(error) = player_stats::update_default(|stats| stats.add_start(board)) {
+                self.results_error = Some(format!("Couldn't save the statistics: {error}"));
+            }
+
+            self.is_start_recorded = true;
+        }
+
+        let MinesweeperStatus::End(is_victory) = status else {
+            return;
+        };
+        if self.is_result_recorded {
+            return;
+        }
+
+        let stats = self.game.get_stats();
+        if let Err(error) = player_stats::update_default(|player_stats| {
+            player_stats.add_outcome(board, is_victory, stats.time)
+        }) {
+            self.results_error = Some(format!("Couldn't save the statistics: {error}"));
+        }
+
+        if is_victory {
+            let record = Record::new(
+                stats.time,
+                stats.get_three_bv_per_second(),
+                self.game.is_assisted(),
+            );
+
+            match records::add_to_default(board, record) {
+                Ok(place) => self.record_place = place,
+                Err(error) => {
+                    self.results_error = Some(format!("Couldn't save the record: {error}"))
+                }
+            }
+        }
+
+        self.is_result_recorded = true;
+    }
+
     fn toggle_flag(&mut self) -> Result<(), MinesweeperError> {
         if let MinesweeperStatus::On = self.game.get_status() {
             self.game
                 .take_action(MinesweeperAction::FlagCell(self.cursor_position))?;
+            self.record_progress();
         }
 
         Ok(())
@@ -420,6 +998,32 @@ impl AppGame {
         } else {
             // otherwise, ask for confirmation
             self.awaiting_leave_confirmation = !self.awaiting_leave_confirmation;
+            self.save_error = None;
+        }
+    }
+
+    /// Saves the game to the default location and leaves. If the game can't be saved, stays and shows why.
+    fn save_and_leave(&mut self) {
+        let result = save::get_default_save_path()
+            .ok_or_else(|| "the data directory is unknown".to_string())
+            .and_then(|path| {
+                save::save(
+                    &SavedGame {
+                        game: self.game.clone(),
+                        config: self.config,
+                        cursor_position: self.cursor_position,
+                        window_offset: self.window_offset,
+                        is_start_recorded: self.is_start_recorded,
+                        is_result_recorded: self.is_result_recorded,
+                    },
+                    &path,
+                )
+                .map_err(|error| error.to_string())
+            });
+
+        match result {
+            Ok(()) => self.leave(),
+            Err(error) => self.save_error = Some(format!("Couldn't save the game: {error}")),
         }
     }
 
@@ -431,3 +1035,125 @@ impl AppGame {
         self.should_emergency_leave = true;
     }
 }
+
+impl From<SavedGame> for AppGame {
+    /// Resumes a saved game where it's been left.
+    fn from(saved_game: SavedGame) -> Self {
+        AppGame {
+            game: saved_game.game,
+            visible_rows_amount: 0,
+            visible_columns_amount: 0,
+            window_offset: saved_game.window_offset,
+            cursor_position: saved_game.cursor_position,
+            awaiting_leave_confirmation: false,
+            probabilities: None,
+            save_error: None,
+            record_place: None,
+            results_error: None,
+            should_leave: false,
+            should_emergency_leave: false,
+            config: saved_game.config,
+            is_start_recorded: saved_game.is_start_recorded,
+            is_result_recorded: saved_game.is_result_recorded,
+        }
+    }
+}
+
+/// The Records app.rs variant
+#[derive(Debug)]
+pub struct AppRecords {
+    /// All the records kept so far.
+    pub records: records::Records,
+    /// The index of the table being displayed.
+    pub selected_table_index: usize,
+    /// The reason the records couldn't be loaded (if they couldn't).
+    pub error: Option<String>,
+    /// Whether it's time to go back to the menu.
+    should_leave: bool,
+    /// The configuration the menu has been set up for, so that it can be restored when going back.
+    menu_config: GameConfig,
+}
+
+impl AppRecords {
+    /// Loads the records and selects the table of the board configuration the menu is set up for (if it's been played).
+    fn new(menu_config: GameConfig) -> Self {
+        let (records, error) = match records::load_default() {
+            Ok(records) => (records, None),
+            Err(error) => (
+                records::Records::default(),
+                Some(format!("Couldn't load the records: {error}")),
+            ),
+        };
+
+        let board = BoardConfig::from(menu_config);
+        let selected_table_index = records
+            .get_tables()
+            .iter()
+            .position(|table| table.board == board)
+            .unwrap_or(0);
+
+        AppRecords {
+            records,
+            selected_table_index,
+            error,
+            should_leave: false,
+            menu_config,
+        }
+    }
+
+    /// Switches between the tables of different board configurations.
+    fn move_cursor(&mut self, direction: MoveCursorDirection) {
+        let tables_amount = self.records.get_tables().len();
+
+        match direction {
+            Left => self.selected_table_index = self.selected_table_index.saturating_sub(1),
+            Right => {
+                self.selected_table_index = cmp::min(
+                    self.selected_table_index + 1,
+                    tables_amount.saturating_sub(1),
+                )
+            }
+            Up | Down | UpLeft | UpRight | DownLeft | DownRight => (),
+        }
+    }
+
+    fn leave(&mut self) {
+        self.should_leave = true;
+    }
+}
+
+/// The Statistics app.rs variant
+#[derive(Debug)]
+pub struct AppPlayerStats {
+    /// The lifetime statistics of the player.
+    pub player_stats: PlayerStats,
+    /// The reason the statistics couldn't be loaded (if they couldn't).
+    pub error: Option<String>,
+    /// Whether it's time to go back to the menu.
+    should_leave: bool,
+    /// The configuration the menu has been set up for, so that it can be restored when going back.
+    menu_config: GameConfig,
+}
+
+impl AppPlayerStats {
+    fn new(menu_config: GameConfig) -> Self {
+        let (player_stats, error) = match player_stats::load_default() {
+            Ok(player_stats) => (player_stats, None),
+            Err(error) => (
+                PlayerStats::default(),
+                Some(format!("Couldn't load the statistics: {error}")),
+            ),
+        };
+
+        AppPlayerStats {
+            player_stats,
+            error,
+            should_leave: false,
+            menu_config,
+        }
+    }
+
+    fn leave(&mut self) {
+        self.should_leave = true;
+    }
+}
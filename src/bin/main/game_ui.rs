@@ -1,7 +1,12 @@
 //! The game renderer functions.
 
 use crate::app::AppGame;
-use mine_rs::{field::cell::Cell, MinesweeperStatus};
+use mine_rs::{
+    field::{cell::Cell, position::Position},
+    rules::{Grid, Topology},
+    stats::GameStats,
+    MinesweeperStatus,
+};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     prelude::Frame,
@@ -9,7 +14,7 @@ use ratatui::{
     text::Line,
     widgets::{Block, BorderType, Borders, Clear, Paragraph, Row, Table, Widget},
 };
-use std::cmp;
+use std::{cmp, iter};
 
 /// The symbols used as the arrows indicating that there are more cells to the top/left/bottom/right (respectively).
 /// This is used when the field is too big to be fully displayed in the terminal.
@@ -18,22 +23,35 @@ use std::cmp;
 const ARROW_SYMBOLS: [&str; 4] = ["↑", "←", "↓", "→"];
 /// The size (height for up and down, width for left and right) of a single arrow.
 /// This is used for the layouts' sizes calculations.
-const ARROW_SYMBOL_SIZE: u8 = 1;
+const ARROW_SYMBOL_SIZE: u16 = 1;
 /// The number of terminal rows that a single cell occupies (including the margins/paddings/borders if any).
-const CELL_HEIGHT: u8 = 3;
+const CELL_HEIGHT: u16 = 3;
 /// The number of terminal columns that a single cell occupies (including the margins/paddings/borders if any).
-const CELL_WIDTH: u8 = 5;
+const CELL_WIDTH: u16 = 5;
+/// The number of terminal columns the odd rows of the hexagonal grid are shifted to the right by (roughly half a cell).
+const HEXAGONAL_ROW_SHIFT: u16 = CELL_WIDTH / 2;
 
 const CLOSED_CELL_SYMBOL: &str = "███";
 const FLAG_SYMBOL: &str = " 🚩 ";
+const ANTI_FLAG_SYMBOL: &str = " 🚫 ";
 const MINE_SYMBOL: &str = " 💣 ";
+const ANTI_MINE_SYMBOL: &str = " ⭕ ";
+const QUESTION_MARK_SYMBOL: &str = " ❓ ";
 const WRONG_CHOICE_SYMBOL: &str = " ❌ ";
 const CLOCK_SYMBOL: &str = " 🕓 ";
+const LIFE_SYMBOL: &str = " 💖 ";
 const CELL_COLOR: Color = Color::Yellow;
 const CELL_PALE_COLOR: Color = Color::LightYellow;
+const SAFE_CELL_COLOR: Color = Color::Green;
+const MINED_CELL_COLOR: Color = Color::Red;
+const EXPLODED_CELL_COLOR: Color = Color::Red;
+const HINTED_CELL_COLOR: Color = Color::Green;
 const APP_BG_COLOR: Color = Color::White;
 const FIELD_BORDER_COLOR: Color = Color::Yellow;
 const FIELD_BORDER_PALE_COLOR: Color = Color::LightYellow;
+const FIELD_BORDER_TYPE: BorderType = BorderType::Thick;
+/// The border of the field whose edges wrap around (see [`Topology::Torus`]).
+const WRAPPING_FIELD_BORDER_TYPE: BorderType = BorderType::Double;
 const PAUSED_GAME_POPUP_BORDER_COLOR: Color = Color::LightYellow;
 const OUTCOME_POPUP_VICTORY_BORDER_COLOR: Color = Color::Green;
 const OUTCOME_POPUP_LOSS_BORDER_COLOR: Color = Color::Red;
@@ -42,28 +60,39 @@ const INFO_WIDGET_BLOCK_COLOR: Color = Color::LightYellow;
 const REGULAR_TEXT_COLOR: Color = Color::Black;
 const LEGEND_TEXT_COLOR: Color = Color::DarkGray;
 
-const LEGEND_TEXT: [&str; 5] = [
+const LEGEND_TEXT: [&str; 9] = [
     "[↑][←][↓][→] / [w][a][s][d] / [i][j][k][l]: move the cursor",
+    "[HOME][PGUP][END][PGDN]: move the cursor diagonally",
     "[SPACE] / [ENTER]: open the selected cell (or surrounding cells)",
-    "[f]: toggle flag for the selected cell",
+    "[f]: toggle (or add one more) flag, anti-flag or question mark for the selected cell",
+    "[u] / [r]: undo / redo the last action",
+    "[h]: get a hint (the safest cell to open next)",
+    "[t]: toggle the training mode (show the mine probabilities)",
     "[p]: pause the game",
     "[q] / [ESC]: leave",
 ];
 const PAUSED_GAME_POPUP_TEXT: [&str; 3] = ["Paused", "", "(Press [p] to continue)"];
 const VICTORY_LINE_TEXT: &str = "You won! Congratulations!";
 const LOSS_LINE_TEXT: &str = "You lost... Wanna try again?";
+const SEED_LINE_TEXT: &str = "Seed: ";
+const NEW_RECORD_LINE_TEXT: &str = "New record: ";
 const OUTCOME_POPUP_TEXT: [&str; 4] = [
     "",
     "Use:",
     "[SPACE] / [ENTER] to start a new game",
     "[q] / [ESC] to leave back to the menu",
 ];
-const LEAVE_CONFIRMATION_POPUP_TEXT: [&str; 6] = [
+const UNDO_LINE_TEXT: &str = "[u] to undo the last action";
+const ASSISTED_LINE_TEXT: &str = "Assisted: ";
+const METRICS_LINE_TEXT: &str = "3BV: ";
+const CLICKS_LINE_TEXT: &str = "Clicks: ";
+const LEAVE_CONFIRMATION_POPUP_TEXT: [&str; 7] = [
     "Are you sure you want to quit?",
-    "The progress shall not be saved!",
+    "The game can be continued later from the menu if saved.",
     "",
     "Use:",
-    "[SPACE] / [ENTER] - CONFIRM",
+    "[SPACE] / [ENTER] - QUIT WITHOUT SAVING",
+    "[f] - SAVE AND QUIT",
     "[q] / [ESC] - CANCEL",
 ];
 
@@ -72,26 +101,47 @@ pub fn render_game(app: &mut AppGame, frame: &mut Frame) {
     let root_container = frame.size();
 
     // the app.rs layout consists of the field, stats and legend containers.
-    // The stats are represented by the flags-, mines- and time-info containers.
+    // The stats are represented by the flags-, mines-, lives- and time-info containers (the lives are only shown if the
+    // rules give more than one)
+    let lives_amount = app.game.get_rules().lives_amount;
     let (
         field_container,
-        (flags_info_container, mines_info_container, time_info_container),
+        (flags_info_container, mines_info_container, lives_info_container, time_info_container),
         legend_container,
-    ) = create_app_layout(&root_container);
+    ) = create_app_layout(&root_container, lives_amount > 1);
 
     // the amounts of rows and columns we need to show totally (the real field size)
     let (total_rows_amount, total_columns_amount, _) = app.game.get_field().get_size();
 
+    // the odd rows of the hexagonal grid are shifted, which takes some extra width
+    let rows_shift = match app.game.get_rules().grid {
+        Grid::Square => 0,
+        Grid::Hexagonal => HEXAGONAL_ROW_SHIFT,
+    };
+
     // update the amounts of rows and columns that we can actually show (respecting the container's size)
     app.visible_rows_amount = calculate_visible_rows_amount(&field_container, total_rows_amount);
     app.visible_columns_amount =
-        calculate_visible_columns_amount(&field_container, total_columns_amount);
+        calculate_visible_columns_amount(&field_container, total_columns_amount, rows_shift);
+
+    // make sure the window doesn't go beyond the field (e.g., if a game's been resumed in a larger terminal)
+    app.window_offset = Position::new(
+        cmp::min(
+            app.window_offset.row_index,
+            total_rows_amount - app.visible_rows_amount,
+        ),
+        cmp::min(
+            app.window_offset.column_index,
+            total_columns_amount - app.visible_columns_amount,
+        ),
+    );
 
     // the field layout consists of the grid and 4 arrows' (up, left, down and right) containers
     let (grid_container, arrow_containers) = create_field_layout(
         &field_container,
-        app.visible_rows_amount as u16,
-        app.visible_columns_amount as u16,
+        app.visible_rows_amount,
+        app.visible_columns_amount,
+        rows_shift,
     );
 
     // adjust the arrow symbols for the proper alignment and declare the default alignment settings for the arrows
@@ -108,6 +158,9 @@ pub fn render_game(app: &mut AppGame, frame: &mut Frame) {
         &grid_container,
         app.visible_rows_amount,
         app.visible_columns_amount,
+        rows_shift,
+        // the shifted rows are the odd ones of the whole field, not of its visible part
+        |row_index| (row_index + app.window_offset.row_index) % 2 == 1,
     );
 
     // Now, as all the containers are ready (except for the popups' ones - those are generated on-demand), we can
@@ -116,21 +169,24 @@ pub fn render_game(app: &mut AppGame, frame: &mut Frame) {
     // 1. Render the terminal background
     frame.render_widget(Block::default().bg(APP_BG_COLOR), root_container);
 
-    // 2. Render the border around the field
+    // 2. Render the border around the field. The edges that wrap around are shown with a double line
     frame.render_widget(
         Block::default()
             .borders(Borders::ALL)
-            .border_type(BorderType::Thick)
+            .border_type(match app.game.get_rules().topology {
+                Topology::Plane => FIELD_BORDER_TYPE,
+                Topology::Torus => WRAPPING_FIELD_BORDER_TYPE,
+            })
             .border_style(Style::default().fg(FIELD_BORDER_COLOR)),
         field_container,
     );
 
     // 3. Render the arrows above the field border
     [
-        app.window_offset.0 != 0,
-        app.window_offset.1 != 0,
-        app.window_offset.0 != total_rows_amount - app.visible_rows_amount,
-        app.window_offset.1 != total_columns_amount - app.visible_columns_amount,
+        app.window_offset.row_index != 0,
+        app.window_offset.column_index != 0,
+        app.window_offset.row_index != total_rows_amount - app.visible_rows_amount,
+        app.window_offset.column_index != total_columns_amount - app.visible_columns_amount,
     ]
     .iter()
     .enumerate()
@@ -163,37 +219,73 @@ pub fn render_game(app: &mut AppGame, frame: &mut Frame) {
                 .enumerate()
                 .for_each(|(column_index, cell_container)| {
                     // the real indices are those including the window offset
-                    let real_row_index = row_index as u8 + app.window_offset.0;
-                    let real_column_index = column_index as u8 + app.window_offset.1;
+                    let real_position = Position::new(
+                        row_index as u16 + app.window_offset.row_index,
+                        column_index as u16 + app.window_offset.column_index,
+                    );
 
                     let cell = app
                         .game
                         .get_field()
-                        .get_cell((real_row_index, real_column_index))
+                        .get_cell(real_position)
                         .expect("Fatal error: couldn't find the cell by its coordinates.");
 
-                    let is_selected = app.cursor_position == (real_row_index, real_column_index);
+                    let is_selected = app.cursor_position == real_position;
+                    let is_opening = app.game.get_field().check_opening(real_position);
+
+                    let hint = app
+                        .game
+                        .get_hint()
+                        .filter(|hint| hint.position == real_position);
+
+                    // the probabilities only make sense while the game is on
+                    let probability = match app.game.get_status() {
+                        MinesweeperStatus::End(_) => None,
+                        _ => hint.map(|hint| hint.probability).or_else(|| {
+                            app.probabilities
+                                .as_ref()
+                                .and_then(|probabilities| probabilities.get(real_position))
+                        }),
+                    };
 
                     let grid_cell = build_cell_widget(
                         cell,
                         is_selected,
+                        is_opening,
                         app.game.get_status() == &MinesweeperStatus::End(false),
+                        probability,
+                        hint.is_some(),
                     );
                     frame.render_widget(grid_cell, *cell_container)
                 });
         });
     }
 
-    // 5. Render the stats
+    // 5. Render the stats (the anti-flags and the anti-mines are only mentioned if the rules require any)
+    let field = app.game.get_field();
+    let with_anti_mines = field.get_anti_mines_amount() > 0;
     frame.render_widget(
-        build_flags_info_widget(app.game.get_field().get_flagged_cells_amount()),
+        build_flags_info_widget(
+            field.get_flagged_cells_amount(),
+            with_anti_mines.then(|| field.get_anti_flagged_cells_amount()),
+        ),
         flags_info_container,
     );
     frame.render_widget(
-        build_mines_info_widget(app.game.get_field().get_mines_amount()),
+        build_mines_info_widget(
+            field.get_mines_amount(),
+            with_anti_mines.then(|| field.get_anti_mines_amount()),
+        ),
         mines_info_container,
     );
 
+    if let Some(lives_info_container) = lives_info_container {
+        frame.render_widget(
+            build_lives_info_widget(app.game.get_lives_left(), lives_amount),
+            lives_info_container,
+        );
+    }
+
     frame.render_widget(
         build_time_info_widget(format_duration(app.game.get_time())),
         time_info_container,
@@ -214,8 +306,26 @@ pub fn render_game(app: &mut AppGame, frame: &mut Frame) {
 
         let lines: Vec<_> = [first_line]
             .iter()
-            .chain(rest_lines.iter())
             .map(|s| s.to_string())
+            // a victory good enough to get into the records is announced
+            .chain(
+                app.record_place
+                    .map(|place| format!("{NEW_RECORD_LINE_TEXT}#{} on this board!", place + 1)),
+            )
+            .chain(app.results_error.clone())
+            .chain([format!("{SEED_LINE_TEXT}{}", app.game.get_seed())])
+            .chain(format_stats(app.game.get_stats()))
+            // the assisted games are marked as such
+            .chain(app.game.is_assisted().then(|| {
+                format!(
+                    "{ASSISTED_LINE_TEXT}{} hint(s), {} undo(s)",
+                    app.game.get_hints_amount(),
+                    app.game.get_undos_amount()
+                )
+            }))
+            .chain(rest_lines.iter().map(|s| s.to_string()))
+            // let the player know that the game can be brought back if the rules allow to
+            .chain(app.game.can_undo().then(|| UNDO_LINE_TEXT.to_string()))
             .collect();
 
         let border_color = if *is_victory {
@@ -229,17 +339,26 @@ pub fn render_game(app: &mut AppGame, frame: &mut Frame) {
 
     // 8. Render the leave confirmation popup in case the leave has been requested
     if app.awaiting_leave_confirmation {
-        render_popup(
-            frame,
-            LEAVE_CONFIRMATION_POPUP_TEXT.map(|line| line.to_string()),
-            LEAVE_CONFIRMATION_POPUP_BORDER_COLOR,
-        );
+        let lines: Vec<_> = LEAVE_CONFIRMATION_POPUP_TEXT
+            .iter()
+            .map(|s| s.to_string())
+            .chain(
+                app.save_error
+                    .iter()
+                    .flat_map(|error| [String::new(), error.clone()]),
+            )
+            .collect();
+
+        render_popup(frame, lines, LEAVE_CONFIRMATION_POPUP_BORDER_COLOR);
     }
 }
 
 /// The method creates the base grid needed for the application. Namely, we need to show the field, some statistics for
-/// the ongoing game and the controls-legend.
-fn create_app_layout(container: &Rect) -> (Rect, (Rect, Rect, Rect), Rect) {
+/// the ongoing game and the controls-legend. The lives are only among the statistics if `with_lives` is `true`.
+fn create_app_layout(
+    container: &Rect,
+    with_lives: bool,
+) -> (Rect, (Rect, Rect, Option<Rect>, Rect), Rect) {
     // the stats container's height is 3 rows: 2 for borders and one for the contents
     let stats_container_height = 3;
     // the legend container's height is 4 rows (for the controls-related information)
@@ -271,17 +390,20 @@ fn create_app_layout(container: &Rect) -> (Rect, (Rect, Rect, Rect), Rect) {
         .constraints(Constraint::from_percentages([25, 50, 25]))
         .split(app_layout[1])[1];
 
-    // the central one from the above is split into 3 equal sections once again (for the 3 stats-items)
-    let flags_mines_and_time_containers = Layout::default()
+    // the central one from the above is split into 3 equal sections once again (for the 3 stats-items), or into 4 ones
+    // if the lives are to be shown as well
+    let stats_items_amount = if with_lives { 4 } else { 3 };
+    let stats_items_containers = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints(Constraint::from_percentages([33, 34, 33]))
+        .constraints((0..stats_items_amount).map(|_| Constraint::Ratio(1, stats_items_amount)))
         .split(stats_container)
         .to_vec();
 
-    let (flags_info_container, mines_info_container, time_info_container) = (
-        flags_mines_and_time_containers[0],
-        flags_mines_and_time_containers[1],
-        flags_mines_and_time_containers[2],
+    let (flags_info_container, mines_info_container, lives_info_container, time_info_container) = (
+        stats_items_containers[0],
+        stats_items_containers[1],
+        with_lives.then(|| stats_items_containers[2]),
+        stats_items_containers[stats_items_amount as usize - 1],
     );
 
     // the bottom rectangle is split the same fashion as the top one
@@ -295,6 +417,7 @@ fn create_app_layout(container: &Rect) -> (Rect, (Rect, Rect, Rect), Rect) {
         (
             flags_info_container,
             mines_info_container,
+            lives_info_container,
             time_info_container,
         ),
         legend_container,
@@ -305,9 +428,10 @@ fn create_app_layout(container: &Rect) -> (Rect, (Rect, Rect, Rect), Rect) {
 /// of the rows is more than the space available vertically to render all these rows. Therefore, in such cases,
 /// we divide the total available space by the height of a single row to find out how many full rows would fit into
 /// the container.
-fn calculate_visible_rows_amount(field_container: &Rect, total_rows_amount: u8) -> u8 {
+fn calculate_visible_rows_amount(field_container: &Rect, total_rows_amount: u16) -> u16 {
     // the height needed to render the field including the space allocated for the arrows
-    let height_needed = (CELL_HEIGHT * total_rows_amount + (ARROW_SYMBOL_SIZE * 2)) as u32;
+    let height_needed =
+        CELL_HEIGHT as u32 * total_rows_amount as u32 + (ARROW_SYMBOL_SIZE * 2) as u32;
 
     // if the total height needed to render the field is less than or equal to the height of the container
     if height_needed <= field_container.height as u32 {
@@ -316,7 +440,7 @@ fn calculate_visible_rows_amount(field_container: &Rect, total_rows_amount: u8)
     } else {
         // otherwise, the amount of rows to render is calculated based on how many rows could potentially fit into the
         // available container's height subtracting the space allocated for the arrows
-        ((field_container.height - (ARROW_SYMBOL_SIZE as u16) * 2) / (CELL_HEIGHT as u16)) as u8
+        (field_container.height - ARROW_SYMBOL_SIZE * 2) / CELL_HEIGHT
     }
 }
 
@@ -324,9 +448,17 @@ fn calculate_visible_rows_amount(field_container: &Rect, total_rows_amount: u8)
 /// of the columns is more than the space available horizontally to render all these columns. Therefore, in such cases,
 /// we divide the total available space by the width of a single column to find out how many full columns would fit into
 /// the container.
-fn calculate_visible_columns_amount(field_container: &Rect, total_columns_amount: u8) -> u8 {
-    // the width needed to render the field including the space allocated for the arrows
-    let width_needed = (CELL_WIDTH * total_columns_amount + (ARROW_SYMBOL_SIZE * 2)) as u32;
+///
+/// The shift of the rows (if they're shifted) is taken into account as well.
+fn calculate_visible_columns_amount(
+    field_container: &Rect,
+    total_columns_amount: u16,
+    rows_shift: u16,
+) -> u16 {
+    // the width needed to render the field including the space allocated for the arrows and the rows' shift
+    let width_needed = CELL_WIDTH as u32 * total_columns_amount as u32
+        + (ARROW_SYMBOL_SIZE * 2) as u32
+        + rows_shift as u32;
 
     // if the total width needed to render the field is less than or equal to the width of the container
     if width_needed <= field_container.width as u32 {
@@ -334,8 +466,11 @@ fn calculate_visible_columns_amount(field_container: &Rect, total_columns_amount
         total_columns_amount
     } else {
         // otherwise, the amount of columns to render is calculated based on how many columns could potentially fit
-        // into the available container's width subtracting the space allocated for the arrows
-        ((field_container.width - (ARROW_SYMBOL_SIZE as u16) * 2) / (CELL_WIDTH as u16)) as u8
+        // into the available container's width subtracting the space allocated for the arrows and the rows' shift
+        field_container
+            .width
+            .saturating_sub(ARROW_SYMBOL_SIZE * 2 + rows_shift)
+            / CELL_WIDTH
     }
 }
 
@@ -352,11 +487,13 @@ fn create_field_layout(
     game_container: &Rect,
     visible_rows_amount: u16,
     visible_columns_amount: u16,
+    rows_shift: u16,
 ) -> (Rect, [Rect; 4]) {
-    // find the height and width needed to render the required amount of rows and columns (not including the arrows)
+    // find the height and width needed to render the required amount of rows and columns (not including the arrows, but
+    // including the rows' shift)
     let (height_for_rows, width_for_columns) = (
-        visible_rows_amount * CELL_HEIGHT as u16,
-        visible_columns_amount * CELL_WIDTH as u16,
+        visible_rows_amount * CELL_HEIGHT,
+        visible_columns_amount * CELL_WIDTH + rows_shift,
     );
 
     // for the central rectangle allocate exactly as much space as needed to fit all the visible rows. Split the
@@ -365,12 +502,12 @@ fn create_field_layout(
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(cmp::max(
-                ARROW_SYMBOL_SIZE as u16,
+                ARROW_SYMBOL_SIZE,
                 (game_container.height - height_for_rows) / 2,
             )),
             Constraint::Length(height_for_rows),
             Constraint::Length(cmp::max(
-                ARROW_SYMBOL_SIZE as u16,
+                ARROW_SYMBOL_SIZE,
                 (game_container.height - height_for_rows) / 2,
             )),
         ])
@@ -385,12 +522,12 @@ fn create_field_layout(
                 .direction(Direction::Horizontal)
                 .constraints([
                     Constraint::Length(cmp::max(
-                        ARROW_SYMBOL_SIZE as u16,
+                        ARROW_SYMBOL_SIZE,
                         (game_container.width - width_for_columns) / 2,
                     )),
                     Constraint::Length(width_for_columns),
                     Constraint::Length(cmp::max(
-                        ARROW_SYMBOL_SIZE as u16,
+                        ARROW_SYMBOL_SIZE,
                         (game_container.width - width_for_columns) / 2,
                     )),
                 ])
@@ -442,14 +579,14 @@ fn adjust_arrow_symbols(field_container: &Rect, arrow_containers: [Rect; 4]) ->
     // arrow down: pad with new lines until it's on the last line (and thus equal with the field's border)
     arrow_symbols[2] = format!(
         "{}{}",
-        "\n".repeat((arrow_containers[2].height - ARROW_SYMBOL_SIZE as u16) as usize),
+        "\n".repeat((arrow_containers[2].height - ARROW_SYMBOL_SIZE) as usize),
         arrow_symbols[2]
     );
 
     // arrow right: pad with new spaces until it's on the last column (and thus equal with the field's border)
     arrow_symbols[3] = format!(
         "{}{}",
-        " ".repeat((arrow_containers[3].width - ARROW_SYMBOL_SIZE as u16) as usize),
+        " ".repeat((arrow_containers[3].width - ARROW_SYMBOL_SIZE) as usize),
         arrow_symbols[3]
     );
 
@@ -465,22 +602,42 @@ fn adjust_arrow_symbols(field_container: &Rect, arrow_containers: [Rect; 4]) ->
 
 /// The grid layout is what's used to display the cells of the field.
 ///
-/// The container is first divided into equal rows, and then each row is divided into equal cells.
-fn build_grid_layout(container: &Rect, rows_amount: u8, columns_amount: u8) -> Vec<Vec<Rect>> {
+/// The container is first divided into equal rows, and then each row is divided into equal cells. The rows for which
+/// `is_row_shifted` (given a visible row's index) holds are shifted to the right by `rows_shift` columns, while the
+/// rest of the rows leave that space empty at the end instead (this is how the hexagonal grid is drawn).
+fn build_grid_layout(
+    container: &Rect,
+    rows_amount: u16,
+    columns_amount: u16,
+    rows_shift: u16,
+    is_row_shifted: impl Fn(u16) -> bool,
+) -> Vec<Vec<Rect>> {
     // divide the space vertically into rows
     let vertical_layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints((0..rows_amount).map(|_| Constraint::Length(CELL_HEIGHT.into())))
+        .constraints((0..rows_amount).map(|_| Constraint::Length(CELL_HEIGHT)))
         .split(*container);
 
-    // divide each row horizontally into cells
+    // divide each row horizontally into cells, surrounded by the leading and the trailing gaps
     vertical_layout
         .iter()
-        .map(|row| {
+        .enumerate()
+        .map(|(row_index, row)| {
+            let leading_gap = if is_row_shifted(row_index as u16) {
+                rows_shift
+            } else {
+                0
+            };
+
+            let constraints = iter::once(leading_gap)
+                .chain((0..columns_amount).map(|_| CELL_WIDTH))
+                .chain(iter::once(rows_shift - leading_gap))
+                .map(Constraint::Length);
+
             Layout::default()
                 .direction(Direction::Horizontal)
-                .constraints((0..columns_amount).map(|_| Constraint::Length(CELL_WIDTH.into())))
-                .split(*row)
+                .constraints(constraints)
+                .split(*row)[1..=columns_amount as usize]
                 .to_vec()
         })
         .collect::<Vec<_>>()
@@ -547,53 +704,129 @@ fn render_popup(frame: &mut Frame, lines: impl IntoIterator<Item = String>, bord
 /// The function builds a widget (basically, a paragraph) that represents a single cell.
 ///
 /// The function takes as input the library-representation of the cell and a flag which suggests whether the cell is
-/// currently selected or not. Based on that information, it decides what text to render and which colors to use.
-fn build_cell_widget(cell: &Cell, selected: bool, game_lost: bool) -> impl Widget {
-    let symbol = if game_lost && cell.is_flagged() && !cell.is_mined() {
+/// currently selected or not. Based on that information, it decides what text to render and which colors to use. Only
+/// the zeros of the openings are left blank, since with anti-mines a zero might be surrounded by the deadly cells.
+///
+/// In the training mode (as well as for the hinted cell), the probability of the closed cell to be mined is shown
+/// instead of the cell itself.
+fn build_cell_widget(
+    cell: &Cell,
+    selected: bool,
+    opening: bool,
+    game_lost: bool,
+    probability: Option<f64>,
+    hinted: bool,
+) -> impl Widget {
+    // a flag is a wrong choice when there are more or fewer mines in the cell than the flags on it (or when an anti-flag
+    // is put anywhere but on an anti-mine)
+    let symbol = if game_lost
+        && cell.is_flagged()
+        && cell.get_signed_flags_amount() != cell.get_signed_mines_amount()
+    {
         WRONG_CHOICE_SYMBOL.to_string()
+    } else if cell.is_question_marked() {
+        QUESTION_MARK_SYMBOL.to_string()
+    } else if let (false, false, Some(probability)) =
+        (cell.is_open(), cell.is_flagged(), probability)
+    {
+        // the percentage always takes 3 characters at most
+        format!("{:>3}", (probability * 100.0).round() as u8)
     } else if !cell.is_open() && !cell.is_flagged() {
         CLOSED_CELL_SYMBOL.to_string()
+    } else if cell.is_anti_flagged() {
+        ANTI_FLAG_SYMBOL.to_string()
     } else if cell.is_flagged() {
-        FLAG_SYMBOL.to_string()
+        build_counted_symbol(FLAG_SYMBOL, cell.get_flags_amount())
     } else if let Some(adjacent_mines_amount) = cell.get_mines_around_amount() {
-        if adjacent_mines_amount == 0 {
+        if opening {
             "   ".to_string()
         } else {
-            format!(" {adjacent_mines_amount} ")
+            // the numbers of the larger neighbourhoods (as well as the negative ones) might take 2 characters
+            format!("{adjacent_mines_amount:^3}")
         }
+    } else if cell.is_anti_mined() {
+        ANTI_MINE_SYMBOL.to_string()
     } else {
-        MINE_SYMBOL.to_string()
+        build_counted_symbol(MINE_SYMBOL, cell.get_mines_amount())
     };
 
-    let color = if selected {
+    // the mines hit while there are lives left stay open as the exploded ones
+    let color = if hinted {
+        HINTED_CELL_COLOR
+    } else if selected {
         CELL_COLOR
+    } else if cell.is_exploded() && !game_lost {
+        EXPLODED_CELL_COLOR
     } else {
         CELL_PALE_COLOR
     };
 
+    // highlight the cells which are known for sure in the training mode
+    let text_color = match probability {
+        Some(probability) if !cell.is_open() && probability == 0.0 => SAFE_CELL_COLOR,
+        Some(probability) if !cell.is_open() && probability == 1.0 => MINED_CELL_COLOR,
+        _ => color,
+    };
+
     // the cell stying
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Thick)
         .border_style(Style::default().fg(color));
 
-    Paragraph::new(symbol).fg(color).block(block)
+    Paragraph::new(symbol).fg(text_color).block(block)
 }
 
-/// Prepares a paragraph to render as an info-block showing the number of flags placed on the field.
-fn build_flags_info_widget(flags_amount: u16) -> impl Widget {
-    Paragraph::new(flags_amount.to_string())
-        .fg(REGULAR_TEXT_COLOR)
-        .alignment(Alignment::Center)
-        .block(build_info_widget_block(FLAG_SYMBOL.trim()))
+/// Returns the symbol followed by the amount of the things it stands for (e.g., `🚩2` for a cell with two flags) or the
+/// symbol alone if there's just one of them. Either way, the result takes 3 characters.
+fn build_counted_symbol(symbol: &str, amount: u8) -> String {
+    if amount > 1 {
+        format!("{}{amount}", symbol.trim())
+    } else {
+        symbol.to_string()
+    }
+}
+
+/// Returns the amount followed by the other amount marked with the symbol (if any), e.g. `10 (⭕ 3)`.
+fn build_counted_amounts(amount: u32, other_symbol: &str, other_amount: Option<u32>) -> String {
+    match other_amount {
+        Some(other_amount) => format!("{amount} ({} {other_amount})", other_symbol.trim()),
+        None => amount.to_string(),
+    }
 }
 
-/// Prepares a paragraph to render as an info-block showing the total number of mines hidden in the field.
-fn build_mines_info_widget(mines_amount: u16) -> impl Widget {
-    Paragraph::new(mines_amount.to_string())
+/// Prepares a paragraph to render as an info-block showing the number of flags (and anti-flags) placed on the field.
+fn build_flags_info_widget(flags_amount: u32, anti_flags_amount: Option<u32>) -> impl Widget {
+    Paragraph::new(build_counted_amounts(
+        flags_amount,
+        ANTI_FLAG_SYMBOL,
+        anti_flags_amount,
+    ))
+    .fg(REGULAR_TEXT_COLOR)
+    .alignment(Alignment::Center)
+    .block(build_info_widget_block(FLAG_SYMBOL.trim()))
+}
+
+/// Prepares a paragraph to render as an info-block showing the total number of mines (and anti-mines) hidden in the
+/// field.
+fn build_mines_info_widget(mines_amount: u32, anti_mines_amount: Option<u32>) -> impl Widget {
+    Paragraph::new(build_counted_amounts(
+        mines_amount,
+        ANTI_MINE_SYMBOL,
+        anti_mines_amount,
+    ))
+    .fg(REGULAR_TEXT_COLOR)
+    .alignment(Alignment::Center)
+    .block(build_info_widget_block(MINE_SYMBOL.trim()))
+}
+
+/// Prepares a paragraph to render as an info-block showing the number of lives left out of the total number of them,
+/// e.g. `2/3`.
+fn build_lives_info_widget(lives_left: u8, lives_amount: u8) -> impl Widget {
+    Paragraph::new(format!("{lives_left}/{lives_amount}"))
         .fg(REGULAR_TEXT_COLOR)
         .alignment(Alignment::Center)
-        .block(build_info_widget_block(MINE_SYMBOL.trim()))
+        .block(build_info_widget_block(LIFE_SYMBOL.trim()))
 }
 
 /// Prepares a paragraph to render as an info-block showing the time it took from the beginning of the game.
@@ -604,9 +837,9 @@ fn build_time_info_widget(formatted_time: String) -> impl Widget {
         .block(build_info_widget_block(CLOCK_SYMBOL.trim()))
 }
 
-/// A dependency of the 3 methods above (`build_flags_info_widget`, `build_mines_info_widget` and
-/// `build_time_info_widget`) which creates a block used to display all info-blocks.
-fn build_info_widget_block(title: &str) -> Block {
+/// A dependency of the 4 methods above (`build_flags_info_widget`, `build_mines_info_widget`,
+/// `build_lives_info_widget` and `build_time_info_widget`) which creates a block used to display all info-blocks.
+fn build_info_widget_block(title: &str) -> Block<'_> {
     Block::default()
         .title(title)
         .borders(Borders::ALL)
@@ -614,6 +847,38 @@ fn build_info_widget_block(title: &str) -> Block {
         .border_style(Style::default().fg(INFO_WIDGET_BLOCK_COLOR))
 }
 
+/// Formats the statistics of the game as three lines: the difficulty metrics of the field, the clicks and the
+/// efficiency, e.g. `3BV: 25/120 (openings: 9, islands: 14)`, `Clicks: 40 (left: 30, chords: 4, flags: 6, wasted: 2)`
+/// and `3BV/s: 1.25, IOE: 0.63, correctness: 95%`.
+fn format_stats(stats: GameStats) -> [String; 3] {
+    let metrics = stats.metrics;
+    let clicks = stats.clicks;
+
+    [
+        format!(
+            "{METRICS_LINE_TEXT}{}/{} (openings: {}, islands: {})",
+            metrics.solved_three_bv,
+            metrics.three_bv,
+            metrics.openings_amount,
+            metrics.islands_amount
+        ),
+        format!(
+            "{CLICKS_LINE_TEXT}{} (left: {}, chords: {}, flags: {}, wasted: {})",
+            stats.get_clicks_amount(),
+            clicks.left_clicks_amount,
+            clicks.chords_amount,
+            clicks.flags_amount,
+            clicks.wasted_clicks_amount
+        ),
+        format!(
+            "3BV/s: {:.2}, IOE: {:.2}, correctness: {:.0}%",
+            stats.get_three_bv_per_second(),
+            stats.get_ioe(),
+            stats.get_correctness() * 100.0
+        ),
+    ]
+}
+
 /// Formats the duration of the game in seconds as `MM:SS`.
 fn format_duration(seconds: u64) -> String {
     let minutes = seconds / 60;
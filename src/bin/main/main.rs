@@ -2,6 +2,11 @@ mod app;
 mod event;
 mod game_ui;
 mod menu_ui;
+mod player_stats;
+mod player_stats_ui;
+mod records;
+mod records_ui;
+mod save;
 mod tui;
 mod update;
 
@@ -9,7 +14,12 @@ use app::App;
 use clap::Parser;
 use color_eyre::Result;
 use event::{Event, EventHandler};
+use mine_rs::{
+    difficulty::Difficulty,
+    rules::{FirstClickPolicy, GenerationMode, Grid, Neighborhood, Rules, Topology, UndoPolicy},
+};
 use ratatui::{backend::CrosstermBackend, Terminal};
+use std::path::PathBuf;
 use tui::Tui;
 use update::update;
 
@@ -17,19 +27,93 @@ use update::update;
 #[command(author, version, about, long_about = None)]
 struct Args {
     #[arg(short = 'H', long)]
-    height: Option<u8>,
+    height: Option<u16>,
     #[arg(short, long)]
-    width: Option<u8>,
+    width: Option<u16>,
     #[arg(short, long)]
-    mines: Option<u16>,
+    mines: Option<u32>,
+    #[arg(short, long)]
+    seed: Option<u64>,
+    /// One of `beginner`, `intermediate` or `expert`. The height, the width and the amount of mines, if given, take
+    /// precedence over the ones of the preset.
+    #[arg(short, long)]
+    difficulty: Option<Difficulty>,
+    #[arg(long)]
+    no_guess: bool,
+    /// One of `none`, `safe-cell`, `opening` or a radius of the safe area around the first opened cell.
+    #[arg(long)]
+    first_click: Option<FirstClickPolicy>,
+    /// One of `forbidden`, `not-after-loss` or `unrestricted`.
+    #[arg(long)]
+    undo: Option<UndoPolicy>,
+    /// One of `plane` or `torus` (the edges of the field wrap around).
+    #[arg(long)]
+    topology: Option<Topology>,
+    /// One of `square` or `hexagonal`.
+    #[arg(long)]
+    grid: Option<Grid>,
+    /// One of `adjacent`, `orthogonal`, `knight-move` or `extended` (the cells the numbers count the mines among).
+    #[arg(long)]
+    neighborhood: Option<Neighborhood>,
+    /// The maximum number of mines a single cell might hold (from 1 to 9).
+    #[arg(long)]
+    max_mines_per_cell: Option<u8>,
+    /// The number of anti-mines, i.e. the deadly cells that count as -1 towards the numbers around them.
+    #[arg(long)]
+    anti_mines: Option<u32>,
+    /// Whether the flags cycle through a question mark before being removed.
+    #[arg(long)]
+    question_marks: bool,
+    /// Whether opening the surrounding cells of a number flags them instead when they all have to be mined.
+    #[arg(long)]
+    auto_flag: bool,
+    /// The number of mines that might be hit before the game is lost.
+    #[arg(long)]
+    lives: Option<u8>,
+    /// The file to resume a previously saved game from. The rest of the options are ignored if it's given.
+    #[arg(long)]
+    load: Option<PathBuf>,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    let rules = Rules {
+        generation: if args.no_guess {
+            GenerationMode::NoGuess
+        } else {
+            GenerationMode::Random
+        },
+        first_click: args.first_click.unwrap_or_default(),
+        undo: args.undo.unwrap_or_default(),
+        topology: args.topology.unwrap_or_default(),
+        grid: args.grid.unwrap_or_default(),
+        neighborhood: args.neighborhood.unwrap_or_default(),
+        max_mines_per_cell: args
+            .max_mines_per_cell
+            .unwrap_or(Rules::default().max_mines_per_cell),
+        anti_mines_amount: args.anti_mines.unwrap_or_default(),
+        question_marks: args.question_marks,
+        auto_flag: args.auto_flag,
+        lives_amount: args.lives.unwrap_or(Rules::default().lives_amount),
+    };
+
     // Create the terminal application.
-    let mut app = App::new(args.height, args.width, args.mines)
-        .expect("Couldn't create the app.rs instance. Bad parameters?");
+    let mut app = if let Some(path) = args.load {
+        App::from_saved_game(save::load(&path)?)
+    } else {
+        // the preset fills in whatever part of the size hasn't been given explicitly
+        let preset_size = args.difficulty.map(|difficulty| difficulty.get_size());
+
+        App::new(
+            args.height.or(preset_size.map(|size| size.0)),
+            args.width.or(preset_size.map(|size| size.1)),
+            args.mines.or(preset_size.map(|size| size.2)),
+            rules,
+            args.seed,
+        )
+        .expect("Couldn't create the app.rs instance. Bad parameters?")
+    };
 
     // Initialize the terminal user interface.
     let backend = CrosstermBackend::new(std::io::stderr());
@@ -1,23 +1,37 @@
 //! The functionality related to the menu renderer.
 
 use crate::app::AppMenu;
-use crate::app::MenuItem::{ColumnsAmount, MinesAmount, RowsAmount};
+use crate::app::MenuItem::{
+    AntiMinesAmount, AutoFlag, ColumnsAmount, Continue, FirstClick, Generation, Grid as GridItem,
+    LivesAmount, MaxMinesPerCell, MinesAmount, Neighborhood as NeighborhoodItem, Preset,
+    QuestionMarks, Records, RowsAmount, Statistics, Topology as TopologyItem, Undo,
+};
+use mine_rs::{
+    difficulty::Difficulty,
+    rules::{FirstClickPolicy, GenerationMode, Grid, Neighborhood, Topology, UndoPolicy},
+};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     prelude::Frame,
     style::{Color, Style, Stylize},
     text::Line,
-    widgets::{Block, BorderType, Borders, Paragraph, Row, Table, Widget},
+    widgets::{
+        block::{Position, Title},
+        Block, BorderType, Borders, Paragraph, Row, Table, Widget,
+    },
 };
+use std::cmp;
 
 const LEGEND_TEXT: [&str; 5] = [
     "[↑][↓] / [w][s] / [i][j][k][l]: select the options",
     "[←][→] / [a][d] / [j][k]: decrement / increment the selected option's value",
-    "[SPACE] / [ENTER]: start the game",
+    "[SPACE] / [ENTER]: start (or continue) the game or show the records / statistics",
     "[f]: restore the selected option's default value",
     "[q] / [ESC]: leave",
 ];
 const LEGEND_TEXT_COLOR: Color = Color::DarkGray;
+/// The number of terminal rows that a single menu item occupies.
+const MENU_ITEM_HEIGHT: u16 = 1;
 
 pub fn render_menu(menu: &mut AppMenu, frame: &mut Frame) {
     // The root container is the whole terminal rectangle.
@@ -27,8 +41,108 @@ pub fn render_menu(menu: &mut AppMenu, frame: &mut Frame) {
     // as the remainder of the height after all the other allocations.
     let (menu_container, error_container, legend_container) = create_app_layout(&root_container);
 
+    // The menu items along with their texts. The saved game can only be continued if there's one.
+    let continue_item = menu
+        .has_saved_game
+        .then(|| (Continue, "Continue the saved game".to_string()));
+    let menu_items = continue_item
+        .into_iter()
+        .chain([
+            (
+                Preset,
+                format!(
+                    "Difficulty: < {} >",
+                    describe_difficulty(menu.get_difficulty())
+                ),
+            ),
+            (ColumnsAmount, format!("Width: < {} >", menu.columns_amount)),
+            (RowsAmount, format!("Height: < {} >", menu.rows_amount)),
+            (MinesAmount, format!("Mines: < {} >", menu.mines_amount)),
+            (
+                Generation,
+                format!(
+                    "Generation: < {} >",
+                    describe_generation_mode(menu.rules.generation)
+                ),
+            ),
+            (
+                FirstClick,
+                format!(
+                    "First click: < {} >",
+                    describe_first_click_policy(menu.rules.first_click)
+                ),
+            ),
+            (
+                Undo,
+                format!("Undo: < {} >", describe_undo_policy(menu.rules.undo)),
+            ),
+            (
+                TopologyItem,
+                format!("Topology: < {} >", describe_topology(menu.rules.topology)),
+            ),
+            (
+                GridItem,
+                format!("Grid: < {} >", describe_grid(menu.rules.grid)),
+            ),
+            (
+                NeighborhoodItem,
+                format!(
+                    "Neighbourhood: < {} >",
+                    describe_neighborhood(menu.rules.neighborhood)
+                ),
+            ),
+            (
+                MaxMinesPerCell,
+                format!("Mines per cell: < {} >", menu.rules.max_mines_per_cell),
+            ),
+            (
+                AntiMinesAmount,
+                format!("Anti-mines: < {} >", menu.rules.anti_mines_amount),
+            ),
+            (
+                QuestionMarks,
+                format!(
+                    "Question marks: < {} >",
+                    describe_toggle(menu.rules.question_marks)
+                ),
+            ),
+            (
+                AutoFlag,
+                format!("Auto-flag: < {} >", describe_toggle(menu.rules.auto_flag)),
+            ),
+            (
+                LivesAmount,
+                format!("Lives: < {} >", menu.rules.lives_amount),
+            ),
+            (Records, "Records".to_string()),
+            (Statistics, "Statistics".to_string()),
+        ])
+        .collect::<Vec<_>>();
+
+    // The items that don't fit into the terminal get scrolled, keeping the selected one in the middle when possible.
+    let visible_items_amount = cmp::min(
+        (menu_container.height.saturating_sub(2) / MENU_ITEM_HEIGHT) as usize,
+        menu_items.len(),
+    );
+    let selected_item_index = menu_items
+        .iter()
+        .position(|(item, _)| menu.selected_item == *item)
+        .unwrap_or(0);
+    let first_visible_item_index = cmp::min(
+        selected_item_index.saturating_sub(visible_items_amount / 2),
+        menu_items.len() - visible_items_amount,
+    );
+    let are_items_hidden_above = first_visible_item_index > 0;
+    let are_items_hidden_below = first_visible_item_index + visible_items_amount < menu_items.len();
+    let menu_items = menu_items
+        .into_iter()
+        .skip(first_visible_item_index)
+        .take(visible_items_amount)
+        .collect::<Vec<_>>();
+
     // Here menu gets shrank to some concrete dimensions.
-    let (menu_container, menu_items_containers) = create_menu_layout(&menu_container, 3);
+    let (menu_container, menu_items_containers) =
+        create_menu_layout(&menu_container, menu_items.len() as u16);
 
     // Now, as all the containers are ready (except for the popups' ones - those are generated on-demand), we can
     // actually render the parts of the application into them.
@@ -37,11 +151,10 @@ pub fn render_menu(menu: &mut AppMenu, frame: &mut Frame) {
     frame.render_widget(Block::default().bg(Color::White), root_container);
 
     // Prepare the conditions for checking whether a menu item by some index is currently selected or not.
-    let menu_items_rendering_conditions = [
-        menu.selected_item == ColumnsAmount,
-        menu.selected_item == RowsAmount,
-        menu.selected_item == MinesAmount,
-    ];
+    let menu_items_rendering_conditions = menu_items
+        .iter()
+        .map(|(item, _)| menu.selected_item == *item)
+        .collect::<Vec<bool>>();
 
     // A closure to build a given menu item's style on the fly.
     let build_menu_item_style = |i| {
@@ -59,35 +172,43 @@ pub fn render_menu(menu: &mut AppMenu, frame: &mut Frame) {
     };
 
     // 2. Run through the list of menu items and render them all as paragraphs.
-    vec![
-        format!("\nWidth: < {} >", menu.columns_amount),
-        format!("\nHeight: < {} >", menu.rows_amount),
-        format!("\nMines: < {} >", menu.mines_amount),
-    ]
-    .into_iter()
-    .enumerate()
-    .for_each(|(i, item)| {
-        frame.render_widget(
-            Paragraph::new(item)
+    menu_items
+        .into_iter()
+        .map(|(_, text)| text)
+        .enumerate()
+        .for_each(|(i, item)| {
+            frame.render_widget(
+                Paragraph::new(item)
+                    .alignment(Alignment::Center)
+                    .style(build_menu_item_style(i)),
+                menu_items_containers[i],
+            )
+        });
+
+    // 2. Render the border around the menu, marking the sides beyond which some of the items are hidden.
+    let mut menu_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    if are_items_hidden_above {
+        menu_block = menu_block.title(Title::from("▲").alignment(Alignment::Center));
+    }
+
+    if are_items_hidden_below {
+        menu_block = menu_block.title(
+            Title::from("▼")
                 .alignment(Alignment::Center)
-                .style(build_menu_item_style(i)),
-            menu_items_containers[i],
-        )
-    });
+                .position(Position::Bottom),
+        );
+    }
 
-    // 2. Render the border around the menu.
-    frame.render_widget(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::Yellow)),
-        menu_container,
-    );
+    frame.render_widget(menu_block, menu_container);
 
     // 3. Render the error (if any).
     if let Some(error) = &menu.error {
         frame.render_widget(
-            Paragraph::new(format!("{:?}", error))
+            Paragraph::new(error.as_str())
                 .alignment(Alignment::Center)
                 .red(),
             error_container,
@@ -98,6 +219,88 @@ pub fn render_menu(menu: &mut AppMenu, frame: &mut Frame) {
     frame.render_widget(build_legend_widget(), legend_container);
 }
 
+/// Returns the human-readable name of the difficulty.
+pub fn describe_difficulty(difficulty: Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Beginner => "Beginner",
+        Difficulty::Intermediate => "Intermediate",
+        Difficulty::Expert => "Expert",
+        Difficulty::Custom { .. } => "Custom",
+    }
+}
+
+/// Returns the human-readable name of the difficulty along with the size of the field, e.g. `Expert (30x16, 99 mines)`.
+pub fn describe_difficulty_with_size(difficulty: Difficulty) -> String {
+    let (rows_amount, columns_amount, mines_amount) = difficulty.get_size();
+
+    format!(
+        "{} ({columns_amount}x{rows_amount}, {mines_amount} mines)",
+        describe_difficulty(difficulty)
+    )
+}
+
+/// Returns the human-readable name of the generation mode.
+pub fn describe_generation_mode(generation: GenerationMode) -> &'static str {
+    match generation {
+        GenerationMode::Random => "Random",
+        GenerationMode::NoGuess => "No guess",
+    }
+}
+
+/// Returns the human-readable name of the first click policy.
+pub fn describe_first_click_policy(first_click: FirstClickPolicy) -> String {
+    match first_click {
+        FirstClickPolicy::NoProtection => "No protection".to_string(),
+        FirstClickPolicy::SafeCell => "Safe cell".to_string(),
+        FirstClickPolicy::Opening => "Opening".to_string(),
+        FirstClickPolicy::SafeArea(radius) => format!("Safe area of {radius}"),
+    }
+}
+
+/// Returns the human-readable name of the undo policy.
+pub fn describe_undo_policy(undo: UndoPolicy) -> &'static str {
+    match undo {
+        UndoPolicy::Forbidden => "Forbidden",
+        UndoPolicy::NotAfterLoss => "Not after loss",
+        UndoPolicy::Unrestricted => "Unrestricted",
+    }
+}
+
+/// Returns the human-readable name of the topology.
+pub fn describe_topology(topology: Topology) -> &'static str {
+    match topology {
+        Topology::Plane => "Plane",
+        Topology::Torus => "Torus",
+    }
+}
+
+/// Returns the human-readable name of the grid.
+pub fn describe_grid(grid: Grid) -> &'static str {
+    match grid {
+        Grid::Square => "Square",
+        Grid::Hexagonal => "Hexagonal",
+    }
+}
+
+/// Returns the human-readable name of the neighbourhood.
+pub fn describe_neighborhood(neighborhood: Neighborhood) -> &'static str {
+    match neighborhood {
+        Neighborhood::Adjacent => "Adjacent",
+        Neighborhood::Orthogonal => "Orthogonal",
+        Neighborhood::KnightMove => "Knight's move",
+        Neighborhood::Extended => "Extended",
+    }
+}
+
+/// Returns the human-readable state of a rule that is either on or off (e.g., the question marks).
+pub fn describe_toggle(enabled: bool) -> &'static str {
+    if enabled {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
 /// The function build a layout for the application (this time, the menu). The layout of the menu is represented with
 /// 3 rectangles: one for the menu itself (to hold the menu items), one for displaying a potential error messages and
 /// one for the legend (the in-menu controls description).
@@ -107,8 +310,10 @@ fn create_app_layout(container: &Rect) -> (Rect, Rect, Rect) {
     let error_container_height = 3;
     // The height of the legend is calculated based on the amount of lines in the legend text we need to display.
     let legend_container_height = LEGEND_TEXT.len() as u16;
-    // The menu container's height is all that's left in the parental container.
-    let menu_container_height = container.height - error_container_height - legend_container_height;
+    // The menu container's height is all that's left in the parental container (if anything's left at all).
+    let menu_container_height = container
+        .height
+        .saturating_sub(error_container_height + legend_container_height);
 
     // Create a vector of vertically-stacked rectangles with the pre-defined widths.
     let vertical_layout = Layout::default()
@@ -142,9 +347,9 @@ fn create_app_layout(container: &Rect) -> (Rect, Rect, Rect) {
 }
 
 fn create_menu_layout(container: &Rect, menu_items_amount: u16) -> (Rect, Vec<Rect>) {
-    // The height for the menu is the number of menu items multiplied by one item's height (3) and plus 2 (because of 1
-    // char padding top and bottom).
-    let settings_container_height = 3 * menu_items_amount + 2;
+    // The height for the menu is the number of menu items multiplied by one item's height and plus 2 (because of 1 char
+    // padding top and bottom).
+    let settings_container_height = MENU_ITEM_HEIGHT * menu_items_amount + 2;
     // This is purely a constant.
     let settings_container_width = 40;
 
@@ -152,9 +357,9 @@ fn create_menu_layout(container: &Rect, menu_items_amount: u16) -> (Rect, Vec<Re
     let vertical_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints(Constraint::from_lengths([
-            (container.height - settings_container_height) / 2,
+            container.height.saturating_sub(settings_container_height) / 2,
             settings_container_height,
-            (container.height - settings_container_height) / 2,
+            container.height.saturating_sub(settings_container_height) / 2,
         ]))
         .split(*container);
 
@@ -163,9 +368,9 @@ fn create_menu_layout(container: &Rect, menu_items_amount: u16) -> (Rect, Vec<Re
     let menu_items_container = Layout::default()
         .direction(Direction::Horizontal)
         .constraints(Constraint::from_lengths([
-            (container.width - settings_container_width) / 2,
+            container.width.saturating_sub(settings_container_width) / 2,
             settings_container_width,
-            (container.width - settings_container_width) / 2,
+            container.width.saturating_sub(settings_container_width) / 2,
         ]))
         .split(vertical_layout[1])[1];
 
@@ -175,7 +380,7 @@ fn create_menu_layout(container: &Rect, menu_items_amount: u16) -> (Rect, Vec<Re
         // ...and separate sub-containers for each of the individual menu items.
         Layout::default()
             .direction(Direction::Vertical)
-            .constraints(Constraint::from_lengths([3, 3, 3]))
+            .constraints((0..menu_items_amount).map(|_| Constraint::Length(MENU_ITEM_HEIGHT)))
             .margin(1)
             .split(menu_items_container)
             .to_vec(),
@@ -0,0 +1,224 @@
+//! Keeping the lifetime statistics of the player.
+
+use crate::records::BoardConfig;
+use crate::save;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The name of the file the statistics are kept in.
+const PLAYER_STATS_FILE_NAME: &str = "stats.json";
+
+/// The statistics of the games played on the same board configuration.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BoardStats {
+    pub board: BoardConfig,
+    pub started_amount: u32,
+    pub won_amount: u32,
+    pub lost_amount: u32,
+    /// The time spent on all the won games together.
+    pub victories_time: Duration,
+}
+
+impl BoardStats {
+    fn new(board: BoardConfig) -> Self {
+        BoardStats {
+            board,
+            started_amount: 0,
+            won_amount: 0,
+            lost_amount: 0,
+            victories_time: Duration::ZERO,
+        }
+    }
+
+    /// Returns the share of the won games among the finished ones, from `0.0` to `1.0`.
+    pub fn get_win_rate(&self) -> f64 {
+        get_win_rate(self.won_amount, self.lost_amount)
+    }
+
+    /// Returns the average time it takes to win a game or [`None`] if no games have been won yet.
+    pub fn get_average_victory_time(&self) -> Option<Duration> {
+        (self.won_amount > 0).then(|| self.victories_time / self.won_amount)
+    }
+}
+
+/// The statistics of all the games played so far.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub started_amount: u32,
+    pub won_amount: u32,
+    pub lost_amount: u32,
+    /// The number of the games won in a row up to now.
+    pub current_streak: u32,
+    /// The largest number of the games ever won in a row.
+    pub longest_streak: u32,
+    /// The statistics of every board configuration, in the order the configurations were first played.
+    boards: Vec<BoardStats>,
+}
+
+impl PlayerStats {
+    /// Returns the statistics of every board configuration played so far.
+    pub fn get_boards(&self) -> &[BoardStats] {
+        &self.boards
+    }
+
+    /// Returns the share of the won games among the finished ones, from `0.0` to `1.0`.
+    pub fn get_win_rate(&self) -> f64 {
+        get_win_rate(self.won_amount, self.lost_amount)
+    }
+
+    /// Counts a new game started on the board configuration.
+    pub fn add_start(&mut self, board: BoardConfig) {
+        self.started_amount += 1;
+        self.get_board_mut(board).started_amount += 1;
+    }
+
+    /// Counts the outcome of a game played on the board configuration. The time only matters for the victories.
+    pub fn add_outcome(&mut self, board: BoardConfig, is_victory: bool, time: Duration) {
+        let board_stats = self.get_board_mut(board);
+
+        if is_victory {
+            board_stats.won_amount += 1;
+            board_stats.victories_time += time;
+
+            self.won_amount += 1;
+            self.current_streak += 1;
+            self.longest_streak = self.longest_streak.max(self.current_streak);
+        } else {
+            board_stats.lost_amount += 1;
+
+            self.lost_amount += 1;
+            self.current_streak = 0;
+        }
+    }
+
+    /// Returns the statistics of the board configuration, adding them if the configuration hasn't been played yet.
+    fn get_board_mut(&mut self, board: BoardConfig) -> &mut BoardStats {
+        let index = match self.boards.iter().position(|stats| stats.board == board) {
+            Some(index) => index,
+            None => {
+                self.boards.push(BoardStats::new(board));
+                self.boards.len() - 1
+            }
+        };
+
+        &mut self.boards[index]
+    }
+}
+
+/// Returns the share of the won games among the finished ones (`0.0` if none have been finished yet).
+fn get_win_rate(won_amount: u32, lost_amount: u32) -> f64 {
+    match won_amount + lost_amount {
+        0 => 0.0,
+        finished_amount => won_amount as f64 / finished_amount as f64,
+    }
+}
+
+/// Returns the path the statistics are kept at.
+pub fn get_default_player_stats_path() -> Option<PathBuf> {
+    save::get_data_directory().map(|data_directory| data_directory.join(PLAYER_STATS_FILE_NAME))
+}
+
+/// Reads the statistics from the file by the given path. A missing file simply means nothing has been played yet.
+pub fn load(path: &Path) -> io::Result<PlayerStats> {
+    save::read_json_or_default(path)
+}
+
+/// Writes the statistics to the file by the given path, creating the missing directories along the way.
+pub fn save(player_stats: &PlayerStats, path: &Path) -> io::Result<()> {
+    save::write_json(player_stats, path)
+}
+
+/// Reads the statistics from the default location.
+pub fn load_default() -> io::Result<PlayerStats> {
+    load(&get_default_player_stats_path().ok_or(io::ErrorKind::NotFound)?)
+}
+
+/// Applies the change to the statistics kept at the default location.
+pub fn update_default(change: impl FnOnce(&mut PlayerStats)) -> io::Result<()> {
+    let path = get_default_player_stats_path().ok_or(io::ErrorKind::NotFound)?;
+    let mut player_stats = load(&path)?;
+
+    change(&mut player_stats);
+
+    save(&player_stats, &path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::PlayerStats;
+    use crate::records::BoardConfig;
+    use mine_rs::{difficulty::Difficulty, rules::Rules};
+    use std::time::Duration;
+
+    fn create_board(difficulty: Difficulty) -> BoardConfig {
+        BoardConfig {
+            difficulty,
+            rules: Rules::default(),
+        }
+    }
+
+    #[test]
+    fn the_streaks_are_counted() {
+        let mut player_stats = PlayerStats::default();
+        let board = create_board(Difficulty::Beginner);
+
+        for is_victory in [true, true, true, false, true] {
+            player_stats.add_outcome(board, is_victory, Duration::from_secs(10));
+        }
+        assert_eq!(player_stats.current_streak, 1);
+        assert_eq!(player_stats.longest_streak, 3);
+
+        for _ in 0..3 {
+            player_stats.add_outcome(board, true, Duration::from_secs(10));
+        }
+        assert_eq!(player_stats.current_streak, 4);
+        assert_eq!(player_stats.longest_streak, 4);
+    }
+
+    #[test]
+    fn the_outcomes_are_counted_per_board_configuration() {
+        let mut player_stats = PlayerStats::default();
+        let beginner = create_board(Difficulty::Beginner);
+        let expert = create_board(Difficulty::Expert);
+
+        player_stats.add_start(beginner);
+        player_stats.add_outcome(beginner, true, Duration::from_secs(10));
+        player_stats.add_start(beginner);
+        player_stats.add_outcome(beginner, true, Duration::from_secs(20));
+        player_stats.add_start(expert);
+        player_stats.add_outcome(expert, false, Duration::from_secs(100));
+        player_stats.add_start(beginner);
+        player_stats.add_outcome(beginner, false, Duration::from_secs(5));
+
+        assert_eq!(player_stats.started_amount, 4);
+        assert_eq!(player_stats.get_win_rate(), 0.5);
+
+        let boards = player_stats.get_boards();
+        assert_eq!(boards.len(), 2);
+
+        assert_eq!(boards[0].board, beginner);
+        assert_eq!(boards[0].started_amount, 3);
+        assert_eq!((boards[0].won_amount, boards[0].lost_amount), (2, 1));
+        assert!((boards[0].get_win_rate() - 2.0 / 3.0).abs() < 1e-9);
+        // the time of the losses doesn't count
+        assert_eq!(
+            boards[0].get_average_victory_time(),
+            Some(Duration::from_secs(15))
+        );
+
+        assert_eq!(boards[1].board, expert);
+        assert_eq!(boards[1].get_win_rate(), 0.0);
+        assert_eq!(boards[1].get_average_victory_time(), None);
+    }
+
+    #[test]
+    fn the_win_rate_is_zero_before_any_game_is_finished() {
+        let mut player_stats = PlayerStats::default();
+        player_stats.add_start(create_board(Difficulty::Beginner));
+
+        assert_eq!(player_stats.get_win_rate(), 0.0);
+        assert_eq!(player_stats.get_boards()[0].get_win_rate(), 0.0);
+    }
+}
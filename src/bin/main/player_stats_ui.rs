@@ -0,0 +1,186 @@
+//! The functionality related to the lifetime statistics renderer.
+
+use crate::app::AppPlayerStats;
+use crate::menu_ui::{
+    describe_difficulty, describe_first_click_policy, describe_generation_mode, describe_grid,
+    describe_neighborhood, describe_toggle, describe_topology, describe_undo_policy,
+};
+use crate::player_stats::PlayerStats;
+use crate::records_ui::format_time;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Paragraph, Row, Table, Widget},
+};
+
+const LEGEND_TEXT: [&str; 1] = ["[q] / [ESC]: back to the menu"];
+const LEGEND_TEXT_COLOR: Color = Color::DarkGray;
+/// The number of terminal rows the summary occupies above the statistics of the boards.
+const SUMMARY_HEIGHT: u16 = 4;
+
+pub fn render_player_stats(player_stats: &mut AppPlayerStats, frame: &mut Frame) {
+    // The root container is the whole terminal rectangle.
+    let root_container = frame.size();
+
+    let (summary_container, boards_container, error_container, legend_container) =
+        create_app_layout(&root_container);
+
+    // 1. Render the terminal background.
+    frame.render_widget(Block::default().bg(Color::White), root_container);
+
+    // 2. Render the totals of all the games and the statistics of every board configuration below them.
+    frame.render_widget(
+        build_summary_widget(&player_stats.player_stats),
+        summary_container,
+    );
+    frame.render_widget(
+        build_boards_table_widget(&player_stats.player_stats),
+        boards_container,
+    );
+
+    // 3. Render the error (if any).
+    if let Some(error) = &player_stats.error {
+        frame.render_widget(
+            Paragraph::new(error.as_str())
+                .alignment(Alignment::Center)
+                .red(),
+            error_container,
+        )
+    }
+
+    // 4. Render the legend.
+    frame.render_widget(build_legend_widget(), legend_container);
+}
+
+/// The function builds a layout for the statistics screen: the summary, the boards, the error and the legend
+/// containers stacked vertically. The summary and the boards take 90% of the width and are horizontally centered.
+fn create_app_layout(container: &Rect) -> (Rect, Rect, Rect, Rect) {
+    // The error is a one-liner with a padding of 1 at the top and at the bottom.
+    let error_container_height = 3;
+    let legend_container_height = LEGEND_TEXT.len() as u16;
+    let boards_container_height =
+        container.height - SUMMARY_HEIGHT - error_container_height - legend_container_height;
+
+    let vertical_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(Constraint::from_lengths([
+            SUMMARY_HEIGHT,
+            boards_container_height,
+            error_container_height,
+            legend_container_height,
+        ]))
+        .split(*container)
+        .to_vec();
+
+    let center_horizontally = |container: Rect| {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(Constraint::from_percentages([5, 90, 5]))
+            .split(container)[1]
+    };
+
+    let error_container = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(Constraint::from_percentages([0, 100, 0]))
+        .margin(1)
+        .split(vertical_layout[2])[1];
+
+    (
+        center_horizontally(vertical_layout[0]),
+        center_horizontally(vertical_layout[1]),
+        error_container,
+        center_horizontally(vertical_layout[3]),
+    )
+}
+
+/// Builds the totals of all the games played, e.g. `Started: 12, won: 5, lost: 4 (win rate: 56%)` along with the
+/// streaks.
+fn build_summary_widget(player_stats: &PlayerStats) -> impl Widget {
+    Paragraph::new(vec![
+        Line::from(""),
+        Line::from(format!(
+            "Started: {}, won: {}, lost: {} (win rate: {:.0}%)",
+            player_stats.started_amount,
+            player_stats.won_amount,
+            player_stats.lost_amount,
+            player_stats.get_win_rate() * 100.0
+        )),
+        Line::from(format!(
+            "Current streak: {}, longest streak: {}",
+            player_stats.current_streak, player_stats.longest_streak
+        )),
+    ])
+    .alignment(Alignment::Center)
+    .fg(Color::Yellow)
+}
+
+/// Builds the table of the statistics of every board configuration played so far.
+fn build_boards_table_widget(player_stats: &PlayerStats) -> impl Widget {
+    let rows = player_stats.get_boards().iter().map(|board_stats| {
+        let board = board_stats.board;
+        let (rows_amount, columns_amount, mines_amount) = board.difficulty.get_size();
+
+        Row::new([
+            // the size of the presets is well-known, so only the custom ones get it shown
+            if board.difficulty.is_preset() {
+                describe_difficulty(board.difficulty).to_string()
+            } else {
+                format!("{columns_amount}x{rows_amount}/{mines_amount}")
+            },
+            format!(
+                "{}, {}, {}, {}, {}, {}, up to {} per cell, {} anti-mines, question marks {}, auto-flag {}, {} lives",
+                describe_generation_mode(board.rules.generation),
+                describe_first_click_policy(board.rules.first_click),
+                describe_undo_policy(board.rules.undo),
+                describe_topology(board.rules.topology),
+                describe_grid(board.rules.grid),
+                describe_neighborhood(board.rules.neighborhood),
+                board.rules.max_mines_per_cell,
+                board.rules.anti_mines_amount,
+                describe_toggle(board.rules.question_marks).to_lowercase(),
+                describe_toggle(board.rules.auto_flag).to_lowercase(),
+                board.rules.lives_amount
+            ),
+            board_stats.started_amount.to_string(),
+            format!("{:.0}%", board_stats.get_win_rate() * 100.0),
+            board_stats
+                .get_average_victory_time()
+                .map_or("-".to_string(), format_time),
+        ])
+    });
+
+    Table::new(
+        rows,
+        [
+            Constraint::Length(12),
+            Constraint::Min(20),
+            Constraint::Length(7),
+            Constraint::Length(8),
+            Constraint::Length(9),
+        ],
+    )
+    .header(Row::new(["Difficulty", "Rules", "Started", "Win rate", "Avg. time"]).bold())
+    .fg(Color::Yellow)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow)),
+    )
+}
+
+/// The function builds the ready-to-use legend block (some text that provides information about the controls).
+fn build_legend_widget() -> impl Widget {
+    let rows = LEGEND_TEXT.map(|legend_line| {
+        let cells = legend_line.split_at(legend_line.find(':').expect("Couldn't find the delimiter character (`:`). Double-check the `LEGEND_TEXT` const's contents."));
+
+        Row::new([
+            Line::from(cells.0).alignment(Alignment::Right),
+            Line::from(cells.1).alignment(Alignment::Left),
+        ])
+    });
+
+    Table::new(rows, Constraint::from_percentages([50, 50])).fg(LEGEND_TEXT_COLOR)
+}
@@ -0,0 +1,235 @@
+//! Keeping the best results of the won games for every board configuration.
+
+use crate::app::GameConfig;
+use crate::save;
+use mine_rs::{difficulty::Difficulty, rules::Rules};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The name of the file the records are kept in.
+const RECORDS_FILE_NAME: &str = "records.json";
+/// The number of the best results kept for every board configuration.
+pub const MAX_RECORDS_AMOUNT: usize = 10;
+
+/// Everything that makes the results comparable: the games with the same board configuration are ranked together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoardConfig {
+    /// The difficulty preset or, for the custom games, the exact size of the field.
+    pub difficulty: Difficulty,
+    pub rules: Rules,
+}
+
+impl From<GameConfig> for BoardConfig {
+    fn from(config: GameConfig) -> Self {
+        BoardConfig {
+            difficulty: config.get_difficulty(),
+            rules: config.rules,
+        }
+    }
+}
+
+/// A single result of a won game.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Record {
+    /// The time it took to win the game.
+    pub time: Duration,
+    /// The moment the game has been won at, in seconds since the Unix epoch.
+    pub date: u64,
+    /// The solving speed.
+    pub three_bv_per_second: f64,
+    /// Whether any hints or undos have been used.
+    pub is_assisted: bool,
+}
+
+impl Record {
+    /// Creates a record of a game won just now.
+    pub fn new(time: Duration, three_bv_per_second: f64, is_assisted: bool) -> Self {
+        Record {
+            time,
+            date: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since_epoch| since_epoch.as_secs()),
+            three_bv_per_second,
+            is_assisted,
+        }
+    }
+}
+
+/// The best results of the games with the same board configuration, from the fastest to the slowest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordsTable {
+    pub board: BoardConfig,
+    pub records: Vec<Record>,
+}
+
+/// The records of all the board configurations played so far.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Records {
+    tables: Vec<RecordsTable>,
+}
+
+impl Records {
+    /// Returns the tables of records, one per board configuration, in the order the configurations were first played.
+    pub fn get_tables(&self) -> &[RecordsTable] {
+        &self.tables
+    }
+
+    /// Adds the result to the table of the board configuration if it's among the best ones. Returns the (zero-based)
+    /// place the result has taken, or [`None`] if it's not good enough to get into the table.
+    ///
+    /// Of the results with the same time, the earlier one is ranked higher.
+    pub fn add(&mut self, board: BoardConfig, record: Record) -> Option<usize> {
+        let table_index = match self.tables.iter().position(|table| table.board == board) {
+            Some(index) => index,
+            None => {
+                self.tables.push(RecordsTable {
+                    board,
+                    records: vec![],
+                });
+                self.tables.len() - 1
+            }
+        };
+        let records = &mut self.tables[table_index].records;
+
+        let place = records.partition_point(|existing_record| existing_record.time <= record.time);
+        if place >= MAX_RECORDS_AMOUNT {
+            return None;
+        }
+
+        records.insert(place, record);
+        records.truncate(MAX_RECORDS_AMOUNT);
+
+        Some(place)
+    }
+}
+
+/// Returns the path the records are kept at.
+pub fn get_default_records_path() -> Option<PathBuf> {
+    save::get_data_directory().map(|data_directory| data_directory.join(RECORDS_FILE_NAME))
+}
+
+/// Reads the records from the file by the given path. A missing file simply means there are no records yet.
+pub fn load(path: &Path) -> io::Result<Records> {
+    save::read_json_or_default(path)
+}
+
+/// Writes the records to the file by the given path, creating the missing directories along the way.
+pub fn save(records: &Records, path: &Path) -> io::Result<()> {
+    save::write_json(records, path)
+}
+
+/// Reads the records from the default location.
+pub fn load_default() -> io::Result<Records> {
+    load(&get_default_records_path().ok_or(io::ErrorKind::NotFound)?)
+}
+
+/// Adds the result to the records kept at the default location (see [`Records::add`]).
+pub fn add_to_default(board: BoardConfig, record: Record) -> io::Result<Option<usize>> {
+    let path = get_default_records_path().ok_or(io::ErrorKind::NotFound)?;
+    let mut records = load(&path)?;
+
+    let place = records.add(board, record);
+    if place.is_some() {
+        save(&records, &path)?;
+    }
+
+    Ok(place)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BoardConfig, Record, Records, MAX_RECORDS_AMOUNT};
+    use mine_rs::{difficulty::Difficulty, rules::Rules};
+    use std::time::Duration;
+
+    fn create_board(difficulty: Difficulty) -> BoardConfig {
+        BoardConfig {
+            difficulty,
+            rules: Rules::default(),
+        }
+    }
+
+    fn create_record(seconds: u64, date: u64) -> Record {
+        Record {
+            time: Duration::from_secs(seconds),
+            date,
+            three_bv_per_second: 1.0,
+            is_assisted: false,
+        }
+    }
+
+    #[test]
+    fn the_records_are_ranked_by_time() {
+        let mut records = Records::default();
+        let beginner = create_board(Difficulty::Beginner);
+
+        assert_eq!(records.add(beginner, create_record(30, 0)), Some(0));
+        assert_eq!(records.add(beginner, create_record(10, 1)), Some(0));
+        assert_eq!(records.add(beginner, create_record(20, 2)), Some(1));
+
+        let times = records.get_tables()[0]
+            .records
+            .iter()
+            .map(|record| record.time.as_secs())
+            .collect::<Vec<u64>>();
+        assert_eq!(times, [10, 20, 30]);
+    }
+
+    #[test]
+    fn of_the_records_with_the_same_time_the_earlier_one_is_ranked_higher() {
+        let mut records = Records::default();
+        let beginner = create_board(Difficulty::Beginner);
+
+        records.add(beginner, create_record(10, 0));
+        assert_eq!(records.add(beginner, create_record(10, 1)), Some(1));
+
+        let dates = records.get_tables()[0]
+            .records
+            .iter()
+            .map(|record| record.date)
+            .collect::<Vec<u64>>();
+        assert_eq!(dates, [0, 1]);
+    }
+
+    #[test]
+    fn a_full_table_keeps_only_the_best_records() {
+        let mut records = Records::default();
+        let beginner = create_board(Difficulty::Beginner);
+
+        for index in 0..MAX_RECORDS_AMOUNT as u64 {
+            records.add(beginner, create_record(10 + index, index));
+        }
+
+        // A result not better than the slowest one doesn't get into the full table.
+        assert_eq!(
+            records.add(beginner, create_record(10 + MAX_RECORDS_AMOUNT as u64, 100)),
+            None
+        );
+        assert_eq!(records.add(beginner, create_record(19, 101)), None);
+
+        // A better one pushes the slowest one out.
+        assert_eq!(records.add(beginner, create_record(5, 102)), Some(0));
+
+        let table = &records.get_tables()[0];
+        assert_eq!(table.records.len(), MAX_RECORDS_AMOUNT);
+        assert_eq!(table.records[0].date, 102);
+        assert_eq!(table.records.last().unwrap().time.as_secs(), 18);
+    }
+
+    #[test]
+    fn the_board_configurations_are_ranked_separately() {
+        let mut records = Records::default();
+        let beginner = create_board(Difficulty::Beginner);
+        let expert = create_board(Difficulty::Expert);
+
+        records.add(beginner, create_record(10, 0));
+        assert_eq!(records.add(expert, create_record(100, 1)), Some(0));
+
+        let tables = records.get_tables();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].board, beginner);
+        assert_eq!(tables[1].board, expert);
+    }
+}
@@ -0,0 +1,258 @@
+//! The functionality related to the records renderer.
+
+use crate::app::AppRecords;
+use crate::menu_ui::{
+    describe_difficulty_with_size, describe_first_click_policy, describe_generation_mode,
+    describe_grid, describe_neighborhood, describe_toggle, describe_topology, describe_undo_policy,
+};
+use crate::records::{RecordsTable, MAX_RECORDS_AMOUNT};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Paragraph, Row, Table, Widget},
+};
+use std::time::Duration;
+
+const LEGEND_TEXT: [&str; 2] = [
+    "[←][→] / [a][d] / [j][l]: switch between the boards",
+    "[q] / [ESC]: back to the menu",
+];
+const LEGEND_TEXT_COLOR: Color = Color::DarkGray;
+const NO_RECORDS_TEXT: &str = "No records yet. Win a game to get one!";
+const ASSISTED_RECORD_TEXT: &str = "assisted";
+/// The width of the records table (along with its border).
+const RECORDS_CONTAINER_WIDTH: u16 = 60;
+/// The number of terminal rows the board description occupies above the records.
+const BOARD_DESCRIPTION_HEIGHT: u16 = 3;
+
+pub fn render_records(records: &mut AppRecords, frame: &mut Frame) {
+    // The root container is the whole terminal rectangle.
+    let root_container = frame.size();
+
+    let (records_container, error_container, legend_container) = create_app_layout(&root_container);
+
+    // 1. Render the terminal background.
+    frame.render_widget(Block::default().bg(Color::White), root_container);
+
+    // 2. Render the selected table (or the lack of thereof).
+    let tables = records.records.get_tables();
+    match tables.get(records.selected_table_index) {
+        Some(table) => {
+            let (description_container, table_container) = create_table_layout(&records_container);
+
+            frame.render_widget(
+                build_board_description_widget(table, records.selected_table_index, tables.len()),
+                description_container,
+            );
+            frame.render_widget(build_records_table_widget(table), table_container);
+        }
+        None => frame.render_widget(
+            Paragraph::new(NO_RECORDS_TEXT)
+                .alignment(Alignment::Center)
+                .fg(Color::Yellow),
+            center_vertically(&records_container, 1),
+        ),
+    }
+
+    // 3. Render the error (if any).
+    if let Some(error) = &records.error {
+        frame.render_widget(
+            Paragraph::new(error.as_str())
+                .alignment(Alignment::Center)
+                .red(),
+            error_container,
+        )
+    }
+
+    // 4. Render the legend.
+    frame.render_widget(build_legend_widget(), legend_container);
+}
+
+/// The function builds a layout for the records screen, which is similar to the menu's one: the records, the error and
+/// the legend containers stacked vertically.
+fn create_app_layout(container: &Rect) -> (Rect, Rect, Rect) {
+    // The error is a one-liner with a padding of 1 at the top and at the bottom.
+    let error_container_height = 3;
+    let legend_container_height = LEGEND_TEXT.len() as u16;
+    let records_container_height =
+        container.height - error_container_height - legend_container_height;
+
+    let vertical_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(Constraint::from_lengths([
+            records_container_height,
+            error_container_height,
+            legend_container_height,
+        ]))
+        .split(*container)
+        .to_vec();
+
+    let error_container = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(Constraint::from_percentages([0, 100, 0]))
+        .margin(1)
+        .split(vertical_layout[1])[1];
+
+    let legend_container = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(Constraint::from_percentages([5, 90, 5]))
+        .split(vertical_layout[2])[1];
+
+    (vertical_layout[0], error_container, legend_container)
+}
+
+/// Splits the records container into the board description and the table of records below it, both horizontally
+/// centered.
+fn create_table_layout(container: &Rect) -> (Rect, Rect) {
+    // The table has a header row and a padding of 1 around it (because of the border).
+    let table_container_height = MAX_RECORDS_AMOUNT as u16 + 3;
+    let container = center_vertically(container, BOARD_DESCRIPTION_HEIGHT + table_container_height);
+
+    let width = RECORDS_CONTAINER_WIDTH.min(container.width);
+    let container = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(Constraint::from_lengths([
+            (container.width - width) / 2,
+            width,
+            (container.width - width) / 2,
+        ]))
+        .split(container)[1];
+
+    let vertical_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(Constraint::from_lengths([
+            BOARD_DESCRIPTION_HEIGHT,
+            table_container_height,
+        ]))
+        .split(container);
+
+    (vertical_layout[0], vertical_layout[1])
+}
+
+/// Returns the part of the container of the given height which is centered vertically.
+fn center_vertically(container: &Rect, height: u16) -> Rect {
+    let height = height.min(container.height);
+
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(Constraint::from_lengths([
+            (container.height - height) / 2,
+            height,
+            (container.height - height) / 2,
+        ]))
+        .split(*container)[1]
+}
+
+/// Builds the description of the board configuration the table belongs to, e.g. `Board 1/3: Expert (30x16, 99 mines)`
+/// followed by the rules.
+fn build_board_description_widget(
+    table: &RecordsTable,
+    table_index: usize,
+    tables_amount: usize,
+) -> impl Widget {
+    let board = table.board;
+
+    Paragraph::new(vec![
+        Line::from(format!(
+            "< Board {}/{tables_amount}: {} >",
+            table_index + 1,
+            describe_difficulty_with_size(board.difficulty)
+        )),
+        Line::from(format!(
+            "Generation: {}, first click: {}, undo: {}, topology: {}, grid: {}, neighbourhood: {}, mines per cell: {}, anti-mines: {}, question marks: {}, auto-flag: {}, lives: {}",
+            describe_generation_mode(board.rules.generation),
+            describe_first_click_policy(board.rules.first_click),
+            describe_undo_policy(board.rules.undo),
+            describe_topology(board.rules.topology),
+            describe_grid(board.rules.grid),
+            describe_neighborhood(board.rules.neighborhood),
+            board.rules.max_mines_per_cell,
+            board.rules.anti_mines_amount,
+            describe_toggle(board.rules.question_marks),
+            describe_toggle(board.rules.auto_flag),
+            board.rules.lives_amount
+        )),
+    ])
+    .alignment(Alignment::Center)
+    .fg(Color::Yellow)
+}
+
+/// Builds the table of the best results of a board configuration.
+fn build_records_table_widget(table: &RecordsTable) -> impl Widget {
+    let rows = table.records.iter().enumerate().map(|(place, record)| {
+        Row::new([
+            format!("#{}", place + 1),
+            format_time(record.time),
+            format!("{:.2}", record.three_bv_per_second),
+            format_date(record.date),
+            if record.is_assisted {
+                ASSISTED_RECORD_TEXT.to_string()
+            } else {
+                String::new()
+            },
+        ])
+    });
+
+    Table::new(
+        rows,
+        Constraint::from_lengths([4, 10, 7, 11, ASSISTED_RECORD_TEXT.len() as u16]),
+    )
+    .header(Row::new(["", "Time", "3BV/s", "Date", ""]).bold())
+    .column_spacing(2)
+    .fg(Color::Yellow)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow)),
+    )
+}
+
+/// Formats the time of a record as `MM:SS.CC`.
+pub fn format_time(time: Duration) -> String {
+    let centiseconds = time.as_millis() / 10;
+
+    format!(
+        "{:0>2}:{:0>2}.{:0>2}",
+        centiseconds / 6000,
+        centiseconds / 100 % 60,
+        centiseconds % 100
+    )
+}
+
+/// Formats the number of seconds since the Unix epoch as a `YYYY-MM-DD` date (in UTC).
+fn format_date(timestamp: u64) -> String {
+    // the days are converted to the civil date as per http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = timestamp / 86400 + 719468;
+    let era = days / 146097;
+    let day_of_era = days % 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!("{year}-{month:0>2}-{day:0>2}")
+}
+
+/// The function builds the ready-to-use legend block (some text that provides information about the controls).
+fn build_legend_widget() -> impl Widget {
+    let rows = LEGEND_TEXT.map(|legend_line| {
+        let cells = legend_line.split_at(legend_line.find(':').expect("Couldn't find the delimiter character (`:`). Double-check the `LEGEND_TEXT` const's contents."));
+
+        Row::new([
+            Line::from(cells.0).alignment(Alignment::Right),
+            Line::from(cells.1).alignment(Alignment::Left),
+        ])
+    });
+
+    Table::new(rows, Constraint::from_percentages([50, 50])).fg(LEGEND_TEXT_COLOR)
+}
@@ -0,0 +1,90 @@
+//! Saving the game in progress to a file and restoring it later on.
+
+use crate::app::GameConfig;
+use mine_rs::{field::position::Position, Minesweeper};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The name of the application's directory inside the user's data directory.
+const DATA_DIRECTORY_NAME: &str = "mine_rs";
+/// The name of the file the game is saved to when leaving.
+const SAVE_FILE_NAME: &str = "save.json";
+
+/// Everything needed to resume a game exactly where it was left.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedGame {
+    /// The game itself, including the elapsed time.
+    pub game: Minesweeper,
+    /// The configuration the game has been created with, so that a new one can be started once it's over.
+    pub config: GameConfig,
+    /// The position of the selected cell.
+    pub cursor_position: Position,
+    /// The visible part of the field.
+    pub window_offset: Position,
+    /// Whether the start of the game has already been counted in the statistics.
+    #[serde(default)]
+    pub is_start_recorded: bool,
+    /// Whether the game has already ended once (and then been undone), so that it doesn't get into the records and the
+    /// statistics twice.
+    #[serde(default)]
+    pub is_result_recorded: bool,
+}
+
+/// Returns the directory where the application keeps its files or [`None`] if the platform has no notion of one.
+pub fn get_data_directory() -> Option<PathBuf> {
+    dirs::data_dir().map(|data_directory| data_directory.join(DATA_DIRECTORY_NAME))
+}
+
+/// Returns the path the game is saved to when leaving.
+pub fn get_default_save_path() -> Option<PathBuf> {
+    get_data_directory().map(|data_directory| data_directory.join(SAVE_FILE_NAME))
+}
+
+/// Checks whether there's a game saved to the default location.
+pub fn check_saved_game_exists() -> bool {
+    get_default_save_path().is_some_and(|path| path.is_file())
+}
+
+/// Writes the game to the file by the given path, creating the missing directories along the way.
+pub fn save(saved_game: &SavedGame, path: &Path) -> io::Result<()> {
+    write_json(saved_game, path)
+}
+
+/// Reads the game from the file by the given path.
+pub fn load(path: &Path) -> io::Result<SavedGame> {
+    read_json(path)
+}
+
+/// Writes the value as JSON to the file by the given path, creating the missing directories along the way.
+pub fn write_json<T: Serialize>(value: &T, path: &Path) -> io::Result<()> {
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+
+    fs::write(path, serde_json::to_string(value)?)
+}
+
+/// Reads the value from the JSON file by the given path.
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Reads the value from the JSON file by the given path, falling back to the default value if there's no such file
+/// (e.g., when nothing has been written there yet).
+pub fn read_json_or_default<T: DeserializeOwned + Default>(path: &Path) -> io::Result<T> {
+    match read_json(path) {
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+        result => result,
+    }
+}
+
+/// Reads the game from the default location and removes the file, so that the same game can't be resumed twice.
+pub fn take_default() -> io::Result<SavedGame> {
+    let path = get_default_save_path().ok_or(io::ErrorKind::NotFound)?;
+    let saved_game = load(&path)?;
+    fs::remove_file(path)?;
+
+    Ok(saved_game)
+}
@@ -9,17 +9,26 @@ pub trait ControlsSupport {
     fn move_cursor(&mut self, direction: MoveCursorDirection);
     fn perform_main_action(&mut self) -> Result<(), MinesweeperError>;
     fn perform_secondary_action(&mut self) -> Result<(), MinesweeperError>;
+    fn undo(&mut self) -> Result<(), MinesweeperError>;
+    fn redo(&mut self) -> Result<(), MinesweeperError>;
+    fn hint(&mut self) -> Result<(), MinesweeperError>;
+    fn toggle_training_mode(&mut self);
     fn pause(&mut self);
     fn leave(&mut self, force: bool);
 }
 
-/// The available directions to move the cursor to.
+/// The available directions to move the cursor to. The diagonal ones are mostly useful on the hexagonal grid, where
+/// the cells above and below a cell are not lined up with it.
 #[derive(PartialEq)]
 pub enum MoveCursorDirection {
     Up,
     Left,
     Down,
     Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
 }
 
 pub fn update(app: &mut App, key_event: KeyEvent) -> Result<(), MinesweeperError> {
@@ -30,15 +39,20 @@ pub fn update(app: &mut App, key_event: KeyEvent) -> Result<(), MinesweeperError
         KeyCode::Left | KeyCode::Char('j') | KeyCode::Char('a') => app.move_cursor(Left),
         KeyCode::Down | KeyCode::Char('k') | KeyCode::Char('s') => app.move_cursor(Down),
         KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('d') => app.move_cursor(Right),
+        // the keys are laid out on the numeric keypad the same way as the directions they stand for
+        KeyCode::Home => app.move_cursor(UpLeft),
+        KeyCode::PageUp => app.move_cursor(UpRight),
+        KeyCode::End => app.move_cursor(DownLeft),
+        KeyCode::PageDown => app.move_cursor(DownRight),
         KeyCode::Enter | KeyCode::Char(' ') => app.perform_main_action()?,
         KeyCode::Char('f') => app.perform_secondary_action()?,
+        KeyCode::Char('u') => app.undo()?,
+        KeyCode::Char('r') => app.redo()?,
+        KeyCode::Char('h') => app.hint()?,
+        KeyCode::Char('t') => app.toggle_training_mode(),
         KeyCode::Char('p') => app.pause(),
         KeyCode::Esc | KeyCode::Char('q') => app.leave(false),
-        KeyCode::Char('c') => {
-            if key_event.modifiers == KeyModifiers::CONTROL {
-                app.leave(true);
-            }
-        }
+        KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.leave(true),
         _ => {}
     };
 
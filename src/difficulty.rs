@@ -0,0 +1,115 @@
+//! The standard difficulty presets.
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// The size of the field along with the amount of mines on it. Besides the arbitrary custom configuration, there are
+/// three standard presets that the results are usually compared within.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Difficulty {
+    /// The 9x9 field with 10 mines.
+    Beginner,
+    /// The 16x16 field with 40 mines.
+    Intermediate,
+    /// The 16x30 (rows x columns) field with 99 mines.
+    Expert,
+    /// Any other configuration.
+    Custom {
+        rows_amount: u16,
+        columns_amount: u16,
+        mines_amount: u32,
+    },
+}
+
+impl Difficulty {
+    /// All the standard presets, from the easiest to the hardest one.
+    pub const PRESETS: [Difficulty; 3] = [
+        Difficulty::Beginner,
+        Difficulty::Intermediate,
+        Difficulty::Expert,
+    ];
+
+    /// Returns the difficulty of the field of the given size. The presets are recognized, so that e.g. the 9x9 field
+    /// with 10 mines is always the [`Difficulty::Beginner`], not a custom one.
+    pub fn from_size(rows_amount: u16, columns_amount: u16, mines_amount: u32) -> Self {
+        Difficulty::PRESETS
+            .into_iter()
+            .find(|preset| preset.get_size() == (rows_amount, columns_amount, mines_amount))
+            .unwrap_or(Difficulty::Custom {
+                rows_amount,
+                columns_amount,
+                mines_amount,
+            })
+    }
+
+    /// Returns the amounts of rows, columns and mines.
+    pub fn get_size(&self) -> (u16, u16, u32) {
+        match *self {
+            Difficulty::Beginner => (9, 9, 10),
+            Difficulty::Intermediate => (16, 16, 40),
+            Difficulty::Expert => (16, 30, 99),
+            Difficulty::Custom {
+                rows_amount,
+                columns_amount,
+                mines_amount,
+            } => (rows_amount, columns_amount, mines_amount),
+        }
+    }
+
+    /// Checks whether the difficulty is one of the standard presets.
+    pub fn is_preset(&self) -> bool {
+        !matches!(self, Difficulty::Custom { .. })
+    }
+}
+
+/// Parses one of the presets from either of `beginner`, `intermediate` or `expert`. The custom difficulty has no
+/// textual representation, as it's defined by the size of the field.
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "beginner" => Ok(Difficulty::Beginner),
+            "intermediate" => Ok(Difficulty::Intermediate),
+            "expert" => Ok(Difficulty::Expert),
+            _ => Err(format!(
+                "expected `beginner`, `intermediate` or `expert`, got `{s}`"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Difficulty;
+
+    #[test]
+    fn from_size_recognizes_the_presets() {
+        for preset in Difficulty::PRESETS {
+            let (rows_amount, columns_amount, mines_amount) = preset.get_size();
+            assert_eq!(
+                Difficulty::from_size(rows_amount, columns_amount, mines_amount),
+                preset
+            );
+        }
+
+        // the expert field is wider than it's high, so the transposed one is a custom one
+        let difficulty = Difficulty::from_size(30, 16, 99);
+        assert_eq!(
+            difficulty,
+            Difficulty::Custom {
+                rows_amount: 30,
+                columns_amount: 16,
+                mines_amount: 99
+            }
+        );
+        assert!(!difficulty.is_preset());
+        assert_eq!(difficulty.get_size(), (30, 16, 99));
+    }
+
+    #[test]
+    fn the_presets_are_parsed_from_their_names() {
+        assert_eq!("expert".parse(), Ok(Difficulty::Expert));
+        assert!("custom".parse::<Difficulty>().is_err());
+    }
+}
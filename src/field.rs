@@ -1,40 +1,233 @@
 pub mod cell;
+pub mod metrics;
+pub mod position;
 
+use crate::rules::{GenerationMode, Grid, Rules, Topology, MAX_MINES_PER_CELL};
+use crate::solver;
 use cell::Cell;
-use rand::{prelude::SliceRandom, thread_rng};
+use position::Position;
+use rand::{prelude::SliceRandom, rngs::StdRng, thread_rng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::cmp;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::{Debug, Display, Formatter};
+use std::iter;
+
+/// The maximum number of layouts (both the freshly generated and the repaired ones) to try when looking for a layout
+/// that can be solved without guessing.
+const NO_GUESS_GENERATION_ATTEMPTS_LIMIT: u32 = 10_000;
 
 /// The enum represents all the variants of what can possibly go wrong when working with fields.
 #[derive(Debug, PartialEq)]
 pub enum FieldError {
     /// Used when the user tries to create a field with less than two cells total.
     NotEnoughCells,
-    /// Used when the required number of mines is less than 1 or is more than the total number of cells minus 1 (there
-    /// should always be at least one mine and at least one cell without a mine).
+    /// Used when the required number of mines is less than 1 or is more than the total number of cells minus 1
+    /// multiplied by the maximum number of mines per cell (there should always be at least one mine and at least one
+    /// cell without a mine).
     ///
     /// The value represents the maximum allowed number of mines for the field with the given dimensions.
-    InvalidMinesAmount(u16),
+    InvalidMinesAmount(u32),
     /// Used when the user tries to populate the field with mines and tells it to except some cell, but that cell's
     /// position is incorrect (i.e., the row's and/or the column's indices are beyond the field's bounds).
     ///
-    /// The value represents the requested-to-except cell's row and column indices respectively.
-    InvalidExceptedCellPosition((u8, u8)),
+    /// The value represents the requested-to-except cell's position.
+    InvalidExceptedCellPosition(Position),
     /// Used when trying to populate with mines a field that has already been populated with them.
     ///
     /// The restriction is implied to avoid accidentally re-distributing the mines of a field of an ongoing game.
     MinesAlreadyExist,
+    /// Used when creating a field from a known layout, but one of the mines' positions is beyond the field's bounds.
+    ///
+    /// The value represents the incorrect position.
+    InvalidMinePosition(Position),
+    /// Used when creating a field from a known layout, but one of the mines' positions is listed more times than a
+    /// cell might hold mines (more than once, unless the rules allow for several mines per cell).
+    ///
+    /// The value represents the repeated position.
+    DuplicateMinePosition(Position),
+    /// Used when the field is configured to only accept the layouts that can be solved without guessing, but no such
+    /// layout could be found in a reasonable number of attempts (which might happen when the field is too densely
+    /// mined).
+    NoGuessLayoutNotFound,
+    /// Used when the user tries to create a [`Grid::Hexagonal`] field with the [`Topology::Torus`] topology and an odd
+    /// number of rows, in which case the shifted rows wouldn't line up across the top and bottom edges.
+    OddRowsAmountOnHexagonalTorus,
+    /// Used when the user tries to create a field with a [`Neighborhood`] that's not defined for its [`Grid`] (the
+    /// [`Neighborhood::KnightMove`] one on the [`Grid::Hexagonal`] grid).
+    UnsupportedNeighborhood,
+    /// Used when the rules allow for less than one or more than [`MAX_MINES_PER_CELL`] mines per cell.
+    InvalidMaxMinesPerCell,
+    /// Used when the required number of anti-mines leaves no room for the mines (there should always be at least one
+    /// cell for a mine and at least one cell without either of them).
+    ///
+    /// The value represents the maximum allowed number of anti-mines for the field with the given dimensions.
+    InvalidAntiMinesAmount(u32),
+    /// Used when the rules allow for less than one life.
+    InvalidLivesAmount,
 }
 
 /// The field representation.
 ///
-/// The field is basically a grid (a 2D vector) of cells with a known number of mines.
-#[derive(PartialEq, Eq)]
+/// The field is basically a grid of cells with a known number of mines. The cells are kept in a single flat buffer row
+/// by row, and the numbers of the cells in each of the states the game cares about are maintained along the way, so
+/// that checking for a victory or a loss doesn't require going through the whole field.
+///
+/// The field is (de)serialized as a grid (a 2D vector) of cells, so the counters are recalculated once it's restored.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "SerializedField", into = "SerializedField")]
 pub struct Field {
-    /// The grid of cells of the field. A 2D vector, where the top level represents rows, and the nested vector of each
-    /// row represents cells.
+    /// The cells of the field, row by row.
+    cells: Vec<Cell>,
+    /// The number of rows of the field.
+    rows_amount: u16,
+    /// The number of columns of the field.
+    columns_amount: u16,
+    /// The total number of mines (a cell might hold several of them, depending on the rules).
+    mines_amount: u32,
+    /// The numbers of the cells in each of the states. Must be updated whenever a cell changes.
+    cells_amounts: CellsAmounts,
+    /// The cells changed since the changes have started being recorded, along with their previous states, or [`None`]
+    /// if the changes aren't being recorded (see [`Field::start_recording_changes`]).
+    changed_cells: Option<Vec<(Position, Cell)>>,
+    /// The rules the field is played by.
+    rules: Rules,
+    /// The seed used to initialize the random number generator when distributing the mines.
+    ///
+    /// The same seed (along with the same excepted cell) always results in the very same mines layout.
+    seed: u64,
+}
+
+/// The numbers of the cells of a field in each of the states the game cares about.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct CellsAmounts {
+    /// The number of the mined cells, including the ones with anti-mines (which is `0` until the field gets populated
+    /// with mines).
+    mined_amount: u32,
+    /// The number of the open cells, including the exploded ones.
+    open_amount: u32,
+    /// The total number of the flags put on the cells.
+    flags_amount: u32,
+    /// The number of the anti-flagged cells.
+    anti_flagged_amount: u32,
+    /// The number of the open mined cells.
+    exploded_amount: u32,
+}
+
+impl CellsAmounts {
+    /// Counts the cells in each of the states.
+    fn of<'a>(cells: impl IntoIterator<Item = &'a Cell>) -> Self {
+        let mut cells_amounts = CellsAmounts::default();
+        cells.into_iter().for_each(|cell| cells_amounts.add(cell));
+
+        cells_amounts
+    }
+
+    /// Counts the cell in.
+    fn add(&mut self, cell: &Cell) {
+        self.mined_amount += cell.is_mined() as u32;
+        self.open_amount += cell.is_open() as u32;
+        self.flags_amount += cell.get_flags_amount() as u32;
+        self.anti_flagged_amount += cell.is_anti_flagged() as u32;
+        self.exploded_amount += cell.is_exploded() as u32;
+    }
+
+    /// Counts the (previously counted in) cell out.
+    fn remove(&mut self, cell: &Cell) {
+        self.mined_amount -= cell.is_mined() as u32;
+        self.open_amount -= cell.is_open() as u32;
+        self.flags_amount -= cell.get_flags_amount() as u32;
+        self.anti_flagged_amount -= cell.is_anti_flagged() as u32;
+        self.exploded_amount -= cell.is_exploded() as u32;
+    }
+}
+
+/// The positions of the mines and the anti-mines of a field.
+#[derive(Debug, Default, Clone)]
+struct MinesLayout {
+    /// The positions of the mined cells. A position is listed as many times as the number of mines in the cell.
+    mines_positions: Vec<Position>,
+    /// The positions of the cells with anti-mines.
+    anti_mines_positions: Vec<Position>,
+}
+
+/// The (de)serialized form of a [`Field`]. The grid is a 2D vector, where the top level represents rows, and the nested
+/// vector of each row represents cells.
+#[derive(Serialize, Deserialize)]
+struct SerializedField {
     grid: Vec<Vec<Cell>>,
-    /// The total number of mined cells.
-    mines_amount: u16,
+    mines_amount: u32,
+    rules: Rules,
+    seed: u64,
+}
+
+impl TryFrom<SerializedField> for Field {
+    type Error = String;
+
+    fn try_from(serialized_field: SerializedField) -> Result<Self, Self::Error> {
+        let rows_amount = serialized_field.grid.len();
+        let columns_amount = serialized_field.grid.first().map_or(0, |row| row.len());
+
+        if rows_amount > u16::MAX as usize || columns_amount > u16::MAX as usize {
+            return Err(format!(
+                "the field of {rows_amount}x{columns_amount} cells is too large"
+            ));
+        }
+
+        if serialized_field
+            .grid
+            .iter()
+            .any(|row| row.len() != columns_amount)
+        {
+            return Err("the rows of the field are of different lengths".to_string());
+        }
+
+        if !Self::check_rows_line_up(rows_amount as u16, &serialized_field.rules) {
+            return Err("the hexagonal torus has an odd number of rows".to_string());
+        }
+
+        if !Self::check_neighborhood_supported(&serialized_field.rules) {
+            return Err("the neighbourhood is not defined for the grid".to_string());
+        }
+
+        if !Self::check_max_mines_per_cell_valid(&serialized_field.rules) {
+            return Err(format!(
+                "the maximum number of mines per cell is not within 1..={MAX_MINES_PER_CELL}"
+            ));
+        }
+
+        if !Self::check_lives_amount_valid(&serialized_field.rules) {
+            return Err("there are no lives".to_string());
+        }
+
+        let cells = serialized_field
+            .grid
+            .into_iter()
+            .flatten()
+            .collect::<Vec<Cell>>();
+
+        Ok(Field {
+            cells_amounts: CellsAmounts::of(&cells),
+            changed_cells: None,
+            cells,
+            rows_amount: rows_amount as u16,
+            columns_amount: columns_amount as u16,
+            mines_amount: serialized_field.mines_amount,
+            rules: serialized_field.rules,
+            seed: serialized_field.seed,
+        })
+    }
+}
+
+impl From<Field> for SerializedField {
+    fn from(field: Field) -> Self {
+        SerializedField {
+            grid: field.get_rows().map(|row| row.to_vec()).collect(),
+            mines_amount: field.mines_amount,
+            rules: field.rules,
+            seed: field.seed,
+        }
+    }
 }
 
 impl Field {
@@ -51,170 +244,835 @@ impl Field {
     /// so that an error (if any) could be shown to the player at the configuration stage, rather than after they
     /// actually start playing.
     ///
+    /// The optional seed is used to make the mines distribution reproducible. If it's not provided, a random one is
+    /// generated instead. Either way, the seed in use can later be retrieved with the [`Field::get_seed`] method.
+    ///
     /// The method might fail with [`FieldError::NotEnoughCells`] in case the total requested field's size is less than
     /// two cells or with [`FieldError::InvalidMinesAmount`] in case the requested mines amount is less than one or is
-    /// more than the total number of cells minus 1.
-    pub fn new(rows_amount: u8, columns_amount: u8, mines_amount: u16) -> Result<Self, FieldError> {
-        let cells_amount = rows_amount as u16 * columns_amount as u16;
+    /// more than the total number of cells minus the number of cells guaranteed to be free of mines (which depends on
+    /// the first click policy, but is always at least one) multiplied by the maximum number of mines per cell. On the
+    /// hexagonal torus, it might also fail with [`FieldError::OddRowsAmountOnHexagonalTorus`] in case the number of
+    /// rows is odd, with [`FieldError::UnsupportedNeighborhood`] in case the neighbourhood is not defined for the grid,
+    /// with [`FieldError::InvalidMaxMinesPerCell`] in case the maximum number of mines per cell is out of range, and with
+    /// [`FieldError::InvalidLivesAmount`] in case there are no lives. If
+    /// the rules require some anti-mines, it might also fail with [`FieldError::InvalidAntiMinesAmount`] in case there
+    /// are too many of them, and the anti-mines take up the cells that could otherwise be mined.
+    pub fn new(
+        rows_amount: u16,
+        columns_amount: u16,
+        mines_amount: u32,
+        rules: Rules,
+        seed: Option<u64>,
+    ) -> Result<Self, FieldError> {
+        let cells_amount = rows_amount as u32 * columns_amount as u32;
 
         if cells_amount < 2 {
             // Return an error if there are less than 2 cells total.
-            Err(FieldError::NotEnoughCells)
-        } else if mines_amount < 1 || mines_amount > (cells_amount - 1) {
+            return Err(FieldError::NotEnoughCells);
+        } else if !Self::check_rows_line_up(rows_amount, &rules) {
+            return Err(FieldError::OddRowsAmountOnHexagonalTorus);
+        } else if !Self::check_neighborhood_supported(&rules) {
+            return Err(FieldError::UnsupportedNeighborhood);
+        } else if !Self::check_max_mines_per_cell_valid(&rules) {
+            return Err(FieldError::InvalidMaxMinesPerCell);
+        } else if !Self::check_lives_amount_valid(&rules) {
+            return Err(FieldError::InvalidLivesAmount);
+        }
+
+        // The number of cells around the first opened one that are kept free of mines. When validating, we assume the
+        // worst case, which is the first opened cell being far enough from the field's borders for the whole area to
+        // fit (as much as the field's dimensions allow). Even without any protection, there must be at least one cell
+        // without a mine.
+        let free_cells_amount = Self::get_first_click_safe_radius(&rules)
+            .map(|radius| {
+                let (area_cells_amount, area_height, area_width) =
+                    Self::get_unbounded_area_size(&rules, radius);
+                let bounding_amount = cmp::min(area_height, rows_amount as u32)
+                    * cmp::min(area_width, columns_amount as u32);
+
+                cmp::min(area_cells_amount, bounding_amount)
+            })
+            .unwrap_or(1);
+
+        // At least one of the cells that might be mined must be left for the mines.
+        let max_anti_mines_amount = (cells_amount - free_cells_amount).saturating_sub(1);
+
+        if rules.anti_mines_amount > max_anti_mines_amount {
+            return Err(FieldError::InvalidAntiMinesAmount(max_anti_mines_amount));
+        }
+
+        // Each of the cells that might be mined and are not taken by the anti-mines might hold several mines.
+        let max_mines_amount = (cells_amount - free_cells_amount - rules.anti_mines_amount)
+            * rules.max_mines_per_cell as u32;
+
+        if mines_amount < 1 || mines_amount > max_mines_amount {
             // Return an error if the requested number of mines is incorrect, specifying the maximum possible number of
             // mines that would be correct for a field with the same dimensions.
-            Err(FieldError::InvalidMinesAmount(cells_amount - 1))
+            Err(FieldError::InvalidMinesAmount(max_mines_amount))
         } else {
-            let grid = (0..rows_amount)
-                .map(|row_index| {
-                    (0..columns_amount)
-                        .map(|column_index| Cell::new((row_index, column_index)))
-                        .collect()
+            Ok(Self::create_empty(
+                rows_amount,
+                columns_amount,
+                mines_amount,
+                rules,
+                seed.unwrap_or_else(|| thread_rng().gen()),
+            ))
+        }
+    }
+
+    /// Creates a new [`Field`] with the provided dimensions and the mines placed exactly at the provided positions.
+    ///
+    /// Unlike with [`Field::new`], the field is ready to be played right away: the numbers of the cells are calculated
+    /// immediately, and there's no need (nor a way) to populate it with mines afterwards. This makes it possible to
+    /// share puzzles, to reproduce real fields and to build tools on top of the library.
+    ///
+    /// As the mines are not distributed randomly, the seed of such a field is meaningless and is always `0`. The rules
+    /// related to the mines distribution (such as [`GenerationMode`]) have no effect either.
+    ///
+    /// The method might fail with [`FieldError::NotEnoughCells`] in case the total requested field's size is less than
+    /// two cells, with [`FieldError::InvalidMinePosition`] or [`FieldError::DuplicateMinePosition`] in case some of the
+    /// positions are beyond the field's bounds or are repeated too many times, or with [`FieldError::InvalidMinesAmount`]
+    /// in case there are no mines at all or there are no cells left without a mine. Just like [`Field::new`], it might
+    /// also fail with [`FieldError::OddRowsAmountOnHexagonalTorus`], [`FieldError::UnsupportedNeighborhood`],
+    /// [`FieldError::InvalidMaxMinesPerCell`] or [`FieldError::InvalidLivesAmount`].
+    ///
+    /// If the rules allow for several mines per cell, a position might be listed several times (up to the maximum
+    /// number of mines per cell) to put that many mines into the cell.
+    pub fn from_mine_positions(
+        rows_amount: u16,
+        columns_amount: u16,
+        mines_positions: &[Position],
+        rules: Rules,
+    ) -> Result<Self, FieldError> {
+        Self::from_mine_and_anti_mine_positions(
+            rows_amount,
+            columns_amount,
+            mines_positions,
+            &[],
+            rules,
+        )
+    }
+
+    /// Creates a new [`Field`] with the provided dimensions, the mines placed exactly at the provided positions and the
+    /// anti-mines placed exactly at the other provided positions (see [`Field::from_mine_positions`]).
+    ///
+    /// The number of anti-mines of the rules is replaced with the number of the provided anti-mines' positions. The
+    /// method might fail with [`FieldError::DuplicateMinePosition`] in case an anti-mine's position is repeated or
+    /// coincides with a mine's one, and with the same errors as [`Field::from_mine_positions`] otherwise.
+    pub fn from_mine_and_anti_mine_positions(
+        rows_amount: u16,
+        columns_amount: u16,
+        mines_positions: &[Position],
+        anti_mines_positions: &[Position],
+        rules: Rules,
+    ) -> Result<Self, FieldError> {
+        let cells_amount = rows_amount as u32 * columns_amount as u32;
+
+        if cells_amount < 2 {
+            return Err(FieldError::NotEnoughCells);
+        }
+
+        if !Self::check_rows_line_up(rows_amount, &rules) {
+            return Err(FieldError::OddRowsAmountOnHexagonalTorus);
+        }
+
+        if !Self::check_neighborhood_supported(&rules) {
+            return Err(FieldError::UnsupportedNeighborhood);
+        }
+
+        if !Self::check_max_mines_per_cell_valid(&rules) {
+            return Err(FieldError::InvalidMaxMinesPerCell);
+        }
+
+        if !Self::check_lives_amount_valid(&rules) {
+            return Err(FieldError::InvalidLivesAmount);
+        }
+
+        let mut known_positions: HashMap<Position, u8> = HashMap::new();
+        for &position in mines_positions {
+            if position.row_index >= rows_amount || position.column_index >= columns_amount {
+                return Err(FieldError::InvalidMinePosition(position));
+            }
+
+            let mines_amount = known_positions.entry(position).or_default();
+            if *mines_amount == rules.max_mines_per_cell {
+                return Err(FieldError::DuplicateMinePosition(position));
+            }
+
+            *mines_amount += 1;
+        }
+
+        for &position in anti_mines_positions {
+            if position.row_index >= rows_amount || position.column_index >= columns_amount {
+                return Err(FieldError::InvalidMinePosition(position));
+            }
+
+            // the anti-mine takes up the whole cell
+            if known_positions.insert(position, 1).is_some() {
+                return Err(FieldError::DuplicateMinePosition(position));
+            }
+        }
+
+        if mines_positions.is_empty() || known_positions.len() > (cells_amount - 1) as usize {
+            return Err(FieldError::InvalidMinesAmount(
+                (cells_amount - 1).saturating_sub(anti_mines_positions.len() as u32)
+                    * rules.max_mines_per_cell as u32,
+            ));
+        }
+
+        let mut field = Self::create_empty(
+            rows_amount,
+            columns_amount,
+            mines_positions.len() as u32,
+            Rules {
+                anti_mines_amount: anti_mines_positions.len() as u32,
+                ..rules
+            },
+            0,
+        );
+
+        field.lay_mines(&MinesLayout {
+            mines_positions: mines_positions.to_vec(),
+            anti_mines_positions: anti_mines_positions.to_vec(),
+        });
+
+        Ok(field)
+    }
+
+    /// Returns the radius of the area around the first opened cell that's kept free of mines according to the rules or
+    /// [`None`] if there's no protection at all.
+    ///
+    /// The no-guess generation needs the first opened cell to be at least an opening to have somewhere to start from.
+    pub(crate) fn get_first_click_safe_radius(rules: &Rules) -> Option<u8> {
+        let radius = rules.first_click.get_safe_radius();
+
+        match rules.generation {
+            GenerationMode::Random => radius,
+            GenerationMode::NoGuess => Some(cmp::max(radius.unwrap_or(0), 1)),
+        }
+    }
+
+    /// Checks whether the rows of the field line up with each other across the edges. This only fails for the
+    /// [`Grid::Hexagonal`] field with the [`Topology::Torus`] topology and an odd number of rows: the first and the last
+    /// rows would both be unshifted and thus couldn't interlock.
+    fn check_rows_line_up(rows_amount: u16, rules: &Rules) -> bool {
+        !(rules.grid == Grid::Hexagonal
+            && rules.topology == Topology::Torus
+            && !rows_amount.is_multiple_of(2))
+    }
+
+    /// Checks whether the neighbourhood is defined for the grid.
+    fn check_neighborhood_supported(rules: &Rules) -> bool {
+        rules.neighborhood.get_offsets(rules.grid, 0).is_some()
+    }
+
+    /// Checks whether the maximum number of mines per cell is within the allowed range.
+    fn check_max_mines_per_cell_valid(rules: &Rules) -> bool {
+        (1..=MAX_MINES_PER_CELL).contains(&rules.max_mines_per_cell)
+    }
+
+    /// Checks whether the rules allow for at least one life.
+    fn check_lives_amount_valid(rules: &Rules) -> bool {
+        rules.lives_amount > 0
+    }
+
+    /// Returns the number of cells within the provided radius around a cell of an infinite field (i.e., the largest the
+    /// area could possibly be) along with the height and the width of the area.
+    fn get_unbounded_area_size(rules: &Rules, radius: u8) -> (u32, u32, u32) {
+        let mut area = HashSet::from([(0, 0)]);
+        let mut ring = vec![(0, 0)];
+
+        for _ in 0..radius {
+            ring = ring
+                .into_iter()
+                .flat_map(|(row_index, column_index): (i32, i32)| {
+                    // only the parity of the row matters for the offsets
+                    let offsets = rules
+                        .neighborhood
+                        .get_offsets(rules.grid, row_index.rem_euclid(2) as u16)
+                        .unwrap_or_default();
+
+                    offsets.into_iter().map(move |(row_offset, column_offset)| {
+                        (row_index + row_offset, column_index + column_offset)
+                    })
                 })
+                .filter(|position| area.insert(*position))
                 .collect();
+        }
+
+        let get_span = |indices: Vec<i32>| {
+            let min = indices.iter().min().unwrap();
+            let max = indices.iter().max().unwrap();
+
+            (max - min + 1) as u32
+        };
+
+        (
+            area.len() as u32,
+            get_span(area.iter().map(|position| position.0).collect()),
+            get_span(area.iter().map(|position| position.1).collect()),
+        )
+    }
 
-            Ok(Field { grid, mines_amount })
+    /// Creates a field of closed not flagged empty cells of the provided dimensions.
+    fn create_empty(
+        rows_amount: u16,
+        columns_amount: u16,
+        mines_amount: u32,
+        rules: Rules,
+        seed: u64,
+    ) -> Self {
+        let cells = vec![Cell::new(); rows_amount as usize * columns_amount as usize];
+
+        Field {
+            cells,
+            rows_amount,
+            columns_amount,
+            mines_amount,
+            cells_amounts: CellsAmounts::default(),
+            changed_cells: None,
+            rules,
+            seed,
         }
     }
 
     /// Populates the field with randomly distributed mines, the total amount of which is known from the time when the
     /// field was created.
     ///
-    /// The method also accepts an optional parameter of a cell position to except. The excepted cell is a one that is
-    /// guaranteed not to be mined.
+    /// The method also accepts an optional parameter of a cell position to except, which is supposed to be the first
+    /// cell opened by the player. Depending on the [`FirstClickPolicy`] rule, the excepted cell (and possibly some area
+    /// around it) is guaranteed not to be mined.
     ///
-    /// The method is guaranteed to place exactly the pre-configured number of mines, even after (if) excepting a
-    /// particular cell.
+    /// The method is guaranteed to place exactly the pre-configured number of mines (and of anti-mines, if the rules
+    /// require some), even after (if) excepting a particular cell.
+    ///
+    /// The distribution is fully determined by the field's seed and the excepted cell's position.
+    ///
+    /// With the [`GenerationMode::NoGuess`] rule, the layout is regenerated (or repaired) until it's possible to solve
+    /// it without guessing starting from the excepted cell. Without an excepted cell there's no starting point to solve
+    /// from, so the mines are distributed randomly.
     ///
     /// As a side effect, it also calls the `self::update_mines_around_values` method.
     ///
     /// The method might fail with [`FieldError::InvalidExceptedCellPosition`] in case the excepted row's and/or
     /// column's indices are beyond the field's bounds or with [`FieldError::MinesAlreadyExist`] in case the method is
-    /// called when there are mines in the field already.
+    /// called when there are mines in the field already. With the [`GenerationMode::NoGuess`] rule, it might also fail
+    /// with [`FieldError::NoGuessLayoutNotFound`] if no suitable layout has been found.
     pub fn populate_with_mines(
         &mut self,
-        excepted_cell_position: Option<(u8, u8)>, // `(row_index, column_index)`
+        excepted_cell_position: Option<Position>,
     ) -> Result<(), FieldError> {
-        // Get the number of rows and the width of a single row.
-        let (rows_amount, columns_amount, _) = self.get_size();
-
         // Return an error if the provided excepted cell's position goes beyond the field's dimensions.
-        if let Some((row_index, column_index)) = excepted_cell_position {
-            if (row_index > rows_amount - 1) || (column_index > columns_amount - 1) {
-                return Err(FieldError::InvalidExceptedCellPosition((
-                    row_index,
-                    column_index,
-                )));
+        if let Some(position) = excepted_cell_position {
+            if self.get_cell(position).is_none() {
+                return Err(FieldError::InvalidExceptedCellPosition(position));
             }
         }
 
-        // Flatten the field for an easier interaction with it.
-        let mut flattened_field = self.grid.iter_mut().flatten().collect::<Vec<&mut Cell>>();
-
         // Return an error if there are mines already: can't populate with mines a field that's already been populated.
-        if flattened_field.iter().any(|cell| cell.is_mined()) {
+        if self.check_mines_exist() {
             return Err(FieldError::MinesAlreadyExist);
         }
 
-        // Remove the reference to the excepted cell (if any) to avoid marking it as mined.
-        if let Some((row_index, column_index)) = excepted_cell_position {
-            // `row_index * columns_amount + column_index` is a formula of getting a 1D flattened-vector's index based
-            // on the original 2D vector's coordinates.
-            flattened_field.remove((row_index * columns_amount + column_index) as usize);
-        }
+        // The random number generator is only seeded once, so that the whole process is reproducible.
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        // The positions of the cells that must be kept free of mines according to the first click policy.
+        let excluded_positions = match (
+            excepted_cell_position,
+            Self::get_first_click_safe_radius(&self.rules),
+        ) {
+            (Some(position), Some(radius)) => self.get_area_positions(position, radius),
+            _ => Vec::new(),
+        };
+
+        let mines_layout = match (self.rules.generation, excepted_cell_position) {
+            (GenerationMode::NoGuess, Some(starting_cell_position)) => self
+                .generate_no_guess_mines_layout(
+                    starting_cell_position,
+                    &excluded_positions,
+                    &mut rng,
+                )?,
+            _ => self.generate_random_mines_layout(&excluded_positions, &mut rng),
+        };
+
+        self.lay_mines(&mines_layout);
+
+        Ok(())
+    }
 
-        // Shuffle the mutable borrowings to randomly distribute the mines.
-        let mut rng = thread_rng();
-        flattened_field.shuffle(&mut rng);
+    /// Mines the cells with the positions of the layout and updates the numbers of the cells around them. A cell gets as
+    /// many mines as the number of times its position is listed.
+    fn lay_mines(&mut self, mines_layout: &MinesLayout) {
+        mines_layout.mines_positions.iter().for_each(|position| {
+            self.update_cell(*position, Cell::mine);
+        });
 
-        // Fill the first `number_of_mines` cells with mines.
-        flattened_field
-            .into_iter()
-            .take(self.mines_amount as usize)
-            .for_each(|cell| {
-                cell.mine();
+        mines_layout
+            .anti_mines_positions
+            .iter()
+            .for_each(|position| {
+                self.update_cell(*position, Cell::anti_mine);
             });
 
         self.update_mines_around_values();
+    }
 
-        Ok(())
+    /// Randomly picks the positions for the pre-configured numbers of mines and anti-mines, avoiding the excluded
+    /// positions.
+    fn generate_random_mines_layout(
+        &self,
+        excluded_positions: &[Position],
+        rng: &mut impl Rng,
+    ) -> MinesLayout {
+        let (rows_amount, columns_amount, _) = self.get_size();
+        let mut excluded_positions = excluded_positions.iter().copied().collect::<HashSet<_>>();
+
+        let get_positions = |excluded_positions: &HashSet<Position>| {
+            (0..rows_amount)
+                .flat_map(|row_index| {
+                    (0..columns_amount)
+                        .map(move |column_index| Position::new(row_index, column_index))
+                })
+                .filter(|position| !excluded_positions.contains(position))
+                .collect::<Vec<Position>>()
+        };
+
+        // The anti-mines go first, so that the mines don't take up the cells needed for them. The random number
+        // generator is left untouched when there are no anti-mines, so that the classic layouts stay the same.
+        let mut anti_mines_positions = Vec::new();
+        if self.rules.anti_mines_amount > 0 {
+            anti_mines_positions = get_positions(&excluded_positions);
+            anti_mines_positions.shuffle(rng);
+            anti_mines_positions.truncate(self.rules.anti_mines_amount as usize);
+
+            excluded_positions.extend(anti_mines_positions.iter().copied());
+        }
+
+        let mut mines_positions = get_positions(&excluded_positions)
+            .into_iter()
+            // Each cell is listed as many times as the number of mines it might hold.
+            .flat_map(|position| iter::repeat_n(position, self.rules.max_mines_per_cell as usize))
+            .collect::<Vec<Position>>();
+
+        // Shuffle the positions to randomly distribute the mines and take the first `mines_amount` of them.
+        mines_positions.shuffle(rng);
+        mines_positions.truncate(self.mines_amount as usize);
+
+        MinesLayout {
+            mines_positions,
+            anti_mines_positions,
+        }
     }
 
-    /// The method increments the numerical values of the mined cells' adjacent cells, which represent the number of
-    /// mines around an adjacent cell.
-    fn update_mines_around_values(&mut self) {
-        // Flatten the field for an easier interaction with it.
-        let flattened_field = self.grid.iter_mut().flatten();
-        // Get mutable borrowings for all the mined cells.
-        let cells_with_mines = flattened_field.filter(|cell| cell.is_mined());
+    /// Picks the positions for the pre-configured number of mines, so that the field can be solved by logic alone
+    /// starting from the cell with the provided position.
+    ///
+    /// A random layout is generated first. While the solver gets stuck on it, a mine (or an anti-mine) it couldn't
+    /// figure out is moved somewhere away from the already-solved part of the field. If that's not possible, a new
+    /// random layout is generated instead.
+    fn generate_no_guess_mines_layout(
+        &self,
+        starting_cell_position: Position,
+        excluded_positions: &[Position],
+        rng: &mut impl Rng,
+    ) -> Result<MinesLayout, FieldError> {
+        let mut mines_layout = self.generate_random_mines_layout(excluded_positions, rng);
+
+        for _ in 0..NO_GUESS_GENERATION_ATTEMPTS_LIMIT {
+            let solved_field = self
+                .with_mines(&mines_layout)
+                .solve_from(starting_cell_position);
+
+            if solved_field.check_all_non_mines_open() {
+                return Ok(mines_layout);
+            }
+
+            let deductions = solver::deduce(&solved_field);
+            let closed_cells = solved_field
+                .get_positions()
+                .zip(&solved_field.cells)
+                .filter(|(_, cell)| !cell.is_open())
+                .collect::<Vec<(Position, &Cell)>>();
+
+            // The mines the solver got stuck at: those next to the solved part, which it couldn't figure out.
+            let unresolved_mines_positions = closed_cells
+                .iter()
+                .filter(|(position, cell)| {
+                    cell.is_mined()
+                        && !deductions.mined.contains(position)
+                        && solved_field.check_adjacent_to_open_cell(*position)
+                })
+                .map(|(position, _)| *position)
+                .collect::<Vec<Position>>();
+
+            // The cells where the mines could be moved to: those far enough from the solved part.
+            let remote_empty_cells_positions = closed_cells
+                .iter()
+                .filter(|(position, cell)| {
+                    !cell.is_mined() && !solved_field.check_adjacent_to_open_cell(*position)
+                })
+                .map(|(position, _)| *position)
+                .collect::<Vec<Position>>();
+
+            if let (Some(from), Some(to)) = (
+                unresolved_mines_positions.choose(rng),
+                remote_empty_cells_positions.choose(rng),
+            ) {
+                // Repair the layout by moving one of the problematic mines away (a cell might hold several of them).
+                let positions = if solved_field.get_cell(*from).unwrap().is_anti_mined() {
+                    &mut mines_layout.anti_mines_positions
+                } else {
+                    &mut mines_layout.mines_positions
+                };
+
+                if let Some(index) = positions.iter().position(|position| position == from) {
+                    positions.remove(index);
+                }
+                positions.push(*to);
+            } else {
+                // The layout can't be repaired. Try a completely new one.
+                mines_layout = self.generate_random_mines_layout(excluded_positions, rng);
+            }
+        }
+
+        Err(FieldError::NoGuessLayoutNotFound)
+    }
+
+    /// Creates a pristine copy of the field (of the same dimensions, rules and seed) with the mines placed according to
+    /// the provided layout.
+    fn with_mines(&self, mines_layout: &MinesLayout) -> Field {
+        let mut field = Self::create_empty(
+            self.rows_amount,
+            self.columns_amount,
+            self.mines_amount,
+            self.rules,
+            self.seed,
+        );
+
+        field.lay_mines(mines_layout);
+
+        field
+    }
+
+    /// Opens the cell with the provided position and then keeps opening the cells that the solver proves to be safe,
+    /// until either all of them are open or there's nothing more that could be figured out without guessing.
+    ///
+    /// Returns the field in the state the solver has stopped at.
+    fn solve_from(mut self, starting_cell_position: Position) -> Field {
+        self.open_cell(starting_cell_position);
+
+        while !self.check_all_non_mines_open() {
+            let deductions = solver::deduce(&self);
+
+            if deductions.safe.is_empty() {
+                break;
+            }
+
+            deductions.safe.into_iter().for_each(|position| {
+                self.open_cell(position);
+            });
+        }
+
+        self
+    }
+
+    /// Returns the position shifted from the provided one by the provided numbers of rows and columns or [`None`] if
+    /// it's beyond the field's bounds. On the [`Topology::Torus`], the positions beyond the edges wrap around instead.
+    pub fn get_shifted_position(
+        &self,
+        position: Position,
+        row_offset: i32,
+        column_offset: i32,
+    ) -> Option<Position> {
+        let shift = |index: u16, offset: i32, amount: u16| {
+            let index = index as i32 + offset;
+
+            match self.rules.topology {
+                Topology::Plane => (0..amount as i32).contains(&index).then_some(index as u16),
+                Topology::Torus => Some(index.rem_euclid(amount as i32) as u16),
+            }
+        };
+
+        Some(Position::new(
+            shift(position.row_index, row_offset, self.rows_amount)?,
+            shift(position.column_index, column_offset, self.columns_amount)?,
+        ))
+    }
+
+    /// Returns the positions of all the cells within the provided radius (i.e., reachable in at most that many steps
+    /// between the adjacent cells) around the cell with the provided position, including the cell itself, in the
+    /// row-major order. With the [`Neighborhood::Adjacent`] neighbourhood, the area is a square on the [`Grid::Square`]
+    /// grid and a hexagon on the [`Grid::Hexagonal`] one. It's cut off by the field's edges, unless they wrap around.
+    fn get_area_positions(&self, position: Position, radius: u8) -> Vec<Position> {
+        if self.get_cell(position).is_none() {
+            return Vec::new();
+        }
+
+        // The area grows ring by ring, each of the rings consisting of the yet unseen neighbours of the previous one.
+        let mut area = HashSet::from([position]);
+        let mut ring = vec![position];
+
+        for _ in 0..radius {
+            ring = ring
+                .into_iter()
+                .flat_map(|ring_position| self.get_adjacent_positions(ring_position))
+                .filter(|adjacent_position| area.insert(*adjacent_position))
+                .collect();
+        }
+
+        let mut positions = area.into_iter().collect::<Vec<Position>>();
+        positions.sort();
+
+        positions
+    }
+
+    /// Returns the positions of the cells adjacent to the one with the provided position (in the row-major order) or an
+    /// empty list if there's no cell at the provided position.
+    ///
+    /// The adjacent cells are the ones defined by the [`Neighborhood`] rule: with the default one, a cell has up to 8
+    /// neighbours on the [`Grid::Square`] grid and up to 6 on the [`Grid::Hexagonal`] one. These are the cells that
+    /// the cell's number counts the mines among. The cells at the edges of the [`Topology::Plane`] field have fewer
+    /// neighbours, while on the [`Topology::Torus`] their missing neighbours are found at the opposite edges.
+    pub fn get_adjacent_positions(&self, position: Position) -> Vec<Position> {
+        if self.get_cell(position).is_none() {
+            return Vec::new();
+        }
+
+        // the neighbourhoods not defined for the grid are rejected when the field is created
+        let offsets = self
+            .rules
+            .neighborhood
+            .get_offsets(self.rules.grid, position.row_index)
+            .unwrap_or_default();
+
+        let mut positions = offsets
+            .into_iter()
+            .filter_map(|(row_offset, column_offset)| {
+                self.get_shifted_position(position, row_offset, column_offset)
+            })
+            // a cell of a tiny torus might be a neighbour of itself
+            .filter(|adjacent_position| *adjacent_position != position)
+            .collect::<Vec<Position>>();
+
+        // On a tiny torus, some of the neighbours coincide, and the wrapped ones are out of order.
+        if self.rules.topology == Topology::Torus {
+            positions.sort();
+            positions.dedup();
+        }
+
+        positions
+    }
+
+    /// Checks whether the cell with the given position is an opening, i.e. an empty cell with neither mines nor anti-mines
+    /// around it. Such cells get their neighbours opened automatically.
+    ///
+    /// Unless there are anti-mines in the field, it's the same as the cell's number being 0. With them, the number might
+    /// be 0 even though the cell is surrounded by both mines and anti-mines.
+    pub fn check_opening(&self, position: Position) -> bool {
+        self.get_cell(position)
+            .is_some_and(|cell| cell.get_mines_around_amount() == Some(0))
+            && self
+                .get_adjacent_positions(position)
+                .into_iter()
+                .filter_map(|adjacent_cell_position| self.get_cell(adjacent_cell_position))
+                .all(|adjacent_cell| !adjacent_cell.is_mined())
+    }
+
+    /// Checks whether any of the cell's adjacent cells is open.
+    fn check_adjacent_to_open_cell(&self, position: Position) -> bool {
+        self.get_adjacent_positions(position)
+            .into_iter()
+            .filter_map(|adjacent_cell_position| self.get_cell(adjacent_cell_position))
+            .any(|adjacent_cell| adjacent_cell.is_open())
+    }
 
-        // Get a flat vector of all the mined cells' adjacent cells' positions.
+    /// The method increases the numerical values of the mined cells' adjacent cells, which represent the number of
+    /// mines around an adjacent cell, by the number of mines in the mined cell. An anti-mine decreases them by one
+    /// instead.
+    fn update_mines_around_values(&mut self) {
+        // Get all the mined cells along with their positions.
+        let cells_with_mines = self
+            .get_positions()
+            .zip(&self.cells)
+            .filter(|(_, cell)| cell.is_mined());
+
+        // Get a flat vector of all the mined cells' adjacent cells' positions along with the number of mines of the
+        // mined cell.
         let adjacent_cells_positions = cells_with_mines
             // Get a mined cell's adjacent cells' positions.
-            .flat_map(|cell| cell.get_adjacent_cells_positions())
-            .collect::<Vec<(u8, u8)>>();
+            .flat_map(|(position, cell)| {
+                let mines_amount = cell.get_signed_mines_amount();
+
+                self.get_adjacent_positions(position)
+                    .into_iter()
+                    .map(move |position| (position, mines_amount))
+            })
+            .collect::<Vec<(Position, i16)>>();
 
-        // For each of the adjacent cells, increment their numerical value, representing the quantity of mines around
+        // For each of the adjacent cells, increase their numerical value, representing the quantity of mines around
         // them.
         adjacent_cells_positions
             .into_iter()
-            .for_each(|(row_index, column_index)| {
-                if let Some(cell) = self.get_cell_mut((row_index, column_index)) {
-                    cell.increment_mines_around_amount();
-                }
+            .for_each(|(position, mines_amount)| {
+                self.update_cell(position, |cell| {
+                    cell.increase_mines_around_amount(mines_amount)
+                });
             });
     }
 
     /// Returns the field's height (the number of rows), width (the number of columns) and the two values multiplied,
     /// which is effectively the total number of cells.
-    pub fn get_size(&self) -> (u8, u8, u16) {
-        let rows_amount = self.grid.len() as u8;
-        let columns_amount = self.grid.first().map(|row| row.len()).unwrap_or(0) as u8;
-        let cells_amount = rows_amount as u16 * columns_amount as u16;
+    pub fn get_size(&self) -> (u16, u16, u32) {
+        (
+            self.rows_amount,
+            self.columns_amount,
+            self.cells.len() as u32,
+        )
+    }
 
-        (rows_amount, columns_amount, cells_amount)
+    /// Checks whether the field has already been populated with mines.
+    pub fn check_mines_exist(&self) -> bool {
+        self.cells_amounts.mined_amount > 0
     }
 
-    /// Returns the field's number of mines.
+    /// Returns the field's number of mines (rather than of the mined cells, as a cell might hold several mines).
     ///
     /// Does not care whether the field has already been populated with them or not. Instead, purely reflects the
     /// configured value, rather than the real one.
-    pub fn get_mines_amount(&self) -> u16 {
+    pub fn get_mines_amount(&self) -> u32 {
         self.mines_amount
     }
 
+    /// Returns the field's number of anti-mines (the ones that count as -1 towards the numbers around them).
+    ///
+    /// Just like [`Field::get_mines_amount`], reflects the configured value.
+    pub fn get_anti_mines_amount(&self) -> u32 {
+        self.rules.anti_mines_amount
+    }
+
+    /// Returns the rules the field is played by.
+    pub fn get_rules(&self) -> Rules {
+        self.rules
+    }
+
+    /// Returns the seed used to distribute the mines across the field.
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns all the cells of the field, row by row.
+    pub fn get_cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// Returns the positions of all the cells of the field row by row, i.e. in the same order as the cells returned by
+    /// [`Field::get_cells`].
+    fn get_positions(&self) -> impl Iterator<Item = Position> {
+        let (rows_amount, columns_amount) = (self.rows_amount, self.columns_amount);
+
+        (0..rows_amount).flat_map(move |row_index| {
+            (0..columns_amount).map(move |column_index| Position::new(row_index, column_index))
+        })
+    }
+
+    /// Returns the cells of the field row by row.
+    fn get_rows(&self) -> impl Iterator<Item = &[Cell]> {
+        // `chunks` doesn't accept `0`, but there are no cells to split in such a case anyway.
+        self.cells.chunks(cmp::max(self.columns_amount as usize, 1))
+    }
+
+    /// Returns the index of the cell with the given position in the flat buffer of cells or [`None`] if there's no cell
+    /// at the given position.
+    fn get_index(&self, position: Position) -> Option<usize> {
+        (position.row_index < self.rows_amount && position.column_index < self.columns_amount).then(
+            || {
+                position.row_index as usize * self.columns_amount as usize
+                    + position.column_index as usize
+            },
+        )
+    }
+
     /// Returns a read-only cell reference by its position or [`None`] if there's no cell at the given position.
-    pub fn get_cell(&self, (row_index, column_index): (u8, u8)) -> Option<&Cell> {
-        self.grid
-            .get(row_index as usize)
-            .and_then(|r| r.get(column_index as usize))
+    pub fn get_cell(&self, position: Position) -> Option<&Cell> {
+        self.get_index(position).map(|index| &self.cells[index])
+    }
+
+    /// Applies the change to the cell with the given position, keeping the numbers of the cells in each of the states
+    /// up to date and recording the change if needed. Returns the result of the change or [`None`] if there's no cell
+    /// at the given position.
+    fn update_cell<T>(
+        &mut self,
+        position: Position,
+        change: impl FnOnce(&mut Cell) -> T,
+    ) -> Option<T> {
+        let index = self.get_index(position)?;
+        let cell = &mut self.cells[index];
+        let cell_before = self.changed_cells.is_some().then(|| cell.clone());
+
+        self.cells_amounts.remove(cell);
+        let result = change(cell);
+        self.cells_amounts.add(cell);
+
+        if let (Some(changed_cells), Some(cell_before)) = (&mut self.changed_cells, cell_before) {
+            if cell_before != *cell {
+                changed_cells.push((position, cell_before));
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Starts recording the changes of the cells, so that an action could be taken back without comparing the whole
+    /// field before and after it. The changes recorded so far (if any) are discarded.
+    pub(crate) fn start_recording_changes(&mut self) {
+        self.changed_cells = Some(Vec::new());
+    }
+
+    /// Stops recording the changes of the cells and returns the positions of the changed cells along with their states
+    /// before the change, in the order they've been changed. A cell changed several times is listed once per change.
+    pub(crate) fn take_recorded_changes(&mut self) -> Vec<(Position, Cell)> {
+        self.changed_cells.take().unwrap_or_default()
     }
 
-    /// Returns a mutable cell reference by its position or [`None`] if there's no cell at the given position.
-    fn get_cell_mut(&mut self, (row_index, column_index): (u8, u8)) -> Option<&mut Cell> {
-        self.grid
-            .get_mut(row_index as usize)
-            .and_then(|r| r.get_mut(column_index as usize))
+    /// Puts the cell in place of the one with the given position, e.g. to take back a change made by an action.
+    ///
+    /// Won't produce any effect if the position is beyond the field's bounds.
+    pub(crate) fn replace_cell(&mut self, position: Position, cell: Cell) {
+        self.update_cell(position, |target_cell| *target_cell = cell);
     }
 
     /// Opens a cell by its position.
     ///
-    /// As a side effect, it also recursively opens all the adjacent cells to the given one if its numerical value is 0
-    /// (if the target cell has no mines in it, to put it simpler).
-    pub fn open_cell(&mut self, (row_index, column_index): (u8, u8)) {
-        if let Some(cell) = self.get_cell_mut((row_index, column_index)) {
-            if !cell.is_open() && !cell.is_flagged() {
-                cell.open();
-            } else {
-                return;
+    /// As a side effect, it also opens all the adjacent cells to the given one if its numerical value is 0 (if the
+    /// target cell has no mines around it, to put it simpler), and so on, until the whole opening is revealed. The
+    /// cells are revealed in waves spreading from the target one, without recursion, so even the largest openings are
+    /// fine.
+    ///
+    /// Returns the positions of the cells that have been opened, in the order they have been opened. The list is empty
+    /// if the target cell is already open, flagged or doesn't exist.
+    pub fn open_cell(&mut self, position: Position) -> Vec<Position> {
+        let mut opened_cells_positions = Vec::new();
+        let mut queue = VecDeque::from([position]);
+
+        while let Some(position) = queue.pop_front() {
+            let Some(cell) = self.get_cell(position) else {
+                continue;
+            };
+
+            if cell.is_open() || cell.is_flagged() {
+                continue;
             }
 
-            if let Some(0) = cell.get_mines_around_amount() {
-                cell.get_adjacent_cells_positions()
-                    .into_iter()
-                    .for_each(|cell_position| self.open_cell(cell_position));
+            if self.check_opening(position) {
+                queue.extend(self.get_adjacent_positions(position));
             }
+
+            self.update_cell(position, Cell::open);
+            opened_cells_positions.push(position);
         }
+
+        opened_cells_positions
     }
 
     /// Opens all the cells surrounding the target one.
@@ -223,292 +1081,1517 @@ impl Field {
     /// implementation.
     ///
     /// The method won't produce any effect if the target cell is closed or flagged or if its numerical value is not the
-    /// same as the number of flags placed around it.
-    pub fn open_surrounding_cells(&mut self, (row_index, column_index): (u8, u8)) {
-        if let Some(target_cell) = self.get_cell((row_index, column_index)) {
-            let adjacent_cells_indices = target_cell.get_adjacent_cells_positions();
+    /// same as the number of flags placed around it (some of the cells might hold several flags, an anti-flag counts
+    /// as -1, and an exploded cell counts as flagged with as many flags as it holds mines).
+    ///
+    /// Returns the positions of the cells that have been opened (see [`Field::open_cell`]).
+    pub fn open_surrounding_cells(&mut self, position: Position) -> Vec<Position> {
+        let mut opened_cells_positions = Vec::new();
 
-            let flagged_adjacent_cells_amount = adjacent_cells_indices
+        if let Some(target_cell) = self.get_cell(position) {
+            let adjacent_cells_indices = self.get_adjacent_positions(position);
+
+            let adjacent_flags_amount = adjacent_cells_indices
                 .iter()
-                .filter_map(|(row_index, column_index)| self.get_cell((*row_index, *column_index)))
-                .filter(|adjacent_cell| adjacent_cell.is_flagged())
-                .collect::<Vec<&Cell>>()
-                .len() as u8;
+                .filter_map(|adjacent_cell_position| self.get_cell(*adjacent_cell_position))
+                .map(|adjacent_cell| {
+                    if adjacent_cell.is_exploded() {
+                        adjacent_cell.get_signed_mines_amount() as i32
+                    } else {
+                        adjacent_cell.get_signed_flags_amount() as i32
+                    }
+                })
+                .sum::<i32>();
 
             if let Some(a) = target_cell.get_mines_around_amount() {
                 if target_cell.is_open()
                     && target_cell.get_mines_around_amount().is_some()
-                    && flagged_adjacent_cells_amount == a
+                    && adjacent_flags_amount == a as i32
                 {
                     adjacent_cells_indices
                         .into_iter()
                         .for_each(|adjacent_cell_position| {
-                            self.open_cell(adjacent_cell_position);
+                            opened_cells_positions.extend(self.open_cell(adjacent_cell_position));
                         });
                 };
             }
         }
-    }
 
-    /// Toggles flag for the cell (if any) with the given position.
-    pub fn toggle_cell_flag(&mut self, (row_index, columns_index): (u8, u8)) {
-        if let Some(cell) = self.get_cell_mut((row_index, columns_index)) {
-            cell.toggle_flag();
-        }
+        opened_cells_positions
     }
 
-    /// The method returns the total number of all the currently flagged cells in the field.
+    /// Flags all the closed cells surrounding the target one, if that's the only way to satisfy its number: when there
+    /// are just as many closed cells around it as the number says (or, with several mines per cell, when the number
+    /// requires all of them to hold as many mines as possible). If the rules require anti-mines, the cells get
+    /// anti-flagged instead when the number requires all of them to hold an anti-mine.
     ///
-    /// A use case might be displaying the in-game statistics.
-    pub fn get_flagged_cells_amount(&self) -> u16 {
-        self.grid
+    /// It's the flagging counterpart of [`Field::open_surrounding_cells`]. The method won't produce any effect if the
+    /// target cell is closed.
+    ///
+    /// Returns the positions of the cells whose marks have changed.
+    pub fn flag_surrounding_cells(&mut self, position: Position) -> Vec<Position> {
+        let cells_flags = self.find_surrounding_cells_to_flag(position);
+
+        cells_flags
             .iter()
-            .flatten()
-            .filter(|cell| cell.is_flagged())
-            .collect::<Vec<&Cell>>()
-            .len() as u16
+            .for_each(|(cell_position, signed_flags_amount)| {
+                self.update_cell(*cell_position, |cell| {
+                    cell.set_signed_flags_amount(*signed_flags_amount)
+                });
+            });
+
+        cells_flags
+            .into_iter()
+            .map(|(cell_position, _)| cell_position)
+            .collect()
     }
 
-    /// Checks that there exists at least one mined cell which is open.
+    /// Checks whether [`Field::flag_surrounding_cells`] would change anything around the cell with the given position.
+    pub fn check_surrounding_cells_flaggable(&self, position: Position) -> bool {
+        !self.find_surrounding_cells_to_flag(position).is_empty()
+    }
+
+    /// Finds the closed cells around the target one which have to be flagged (see [`Field::flag_surrounding_cells`])
+    /// along with the number of flags each of them needs (`-1` being an anti-flag). The cells that are already flagged
+    /// as needed are skipped.
+    fn find_surrounding_cells_to_flag(&self, position: Position) -> Vec<(Position, i16)> {
+        let Some(mines_around_amount) = self
+            .get_cell(position)
+            .filter(|cell| cell.is_open())
+            .and_then(|cell| cell.get_mines_around_amount())
+        else {
+            return Vec::new();
+        };
+
+        let (open_cells_positions, closed_cells_positions): (Vec<Position>, Vec<Position>) = self
+            .get_adjacent_positions(position)
+            .into_iter()
+            .partition(|adjacent_cell_position| {
+                self.get_cell(*adjacent_cell_position)
+                    .is_some_and(|adjacent_cell| adjacent_cell.is_open())
+            });
+
+        // an open mine is visible to the player along with the number of mines in it
+        let mines_left = mines_around_amount as i32
+            - open_cells_positions
+                .iter()
+                .filter_map(|cell_position| self.get_cell(*cell_position))
+                .map(|cell| cell.get_signed_mines_amount() as i32)
+                .sum::<i32>();
+        let closed_cells_amount = closed_cells_positions.len() as i32;
+
+        let signed_flags_amount = if closed_cells_amount == 0 {
+            return Vec::new();
+        } else if mines_left == closed_cells_amount * self.rules.max_mines_per_cell as i32 {
+            self.rules.max_mines_per_cell as i16
+        } else if self.rules.anti_mines_amount > 0 && mines_left == -closed_cells_amount {
+            -1
+        } else {
+            return Vec::new();
+        };
+
+        closed_cells_positions
+            .into_iter()
+            .filter(|cell_position| {
+                self.get_cell(*cell_position)
+                    .is_some_and(|cell| cell.get_signed_flags_amount() != signed_flags_amount)
+            })
+            .map(|cell_position| (cell_position, signed_flags_amount))
+            .collect()
+    }
+
+    /// Toggles flag for the cell (if any) with the given position.
+    ///
+    /// If the rules allow for several mines per cell, the flags cycle instead: each call puts one more flag on the cell
+    /// until there are as many of them as the maximum number of mines per cell, and the next call removes them all. If
+    /// the rules require anti-mines, the cell gets an anti-flag before the flags are removed. If the rules allow for
+    /// question marks, the cell gets one right before being cleared.
+    pub fn toggle_cell_flag(&mut self, position: Position) {
+        let max_flags_amount = self.rules.max_mines_per_cell;
+        let with_anti_flag = self.rules.anti_mines_amount > 0;
+        let with_question_mark = self.rules.question_marks;
+        self.update_cell(position, |cell| {
+            cell.cycle_flags(max_flags_amount, with_anti_flag, with_question_mark)
+        });
+    }
+
+    /// The method returns the total number of all the flags currently put in the field, which is the same as the number
+    /// of the flagged cells unless the rules allow for several mines (and thus flags) per cell. Either way, it's the
+    /// number to compare against [`Field::get_mines_amount`].
+    ///
+    /// A use case might be displaying the in-game statistics.
+    pub fn get_flagged_cells_amount(&self) -> u32 {
+        self.cells_amounts.flags_amount
+    }
+
+    /// The method returns the number of the anti-flagged cells, which is the number to compare against
+    /// [`Field::get_anti_mines_amount`].
+    pub fn get_anti_flagged_cells_amount(&self) -> u32 {
+        self.cells_amounts.anti_flagged_amount
+    }
+
+    /// The method returns the number of the open cells, including the exploded ones.
+    pub fn get_open_cells_amount(&self) -> u32 {
+        self.cells_amounts.open_amount
+    }
+
+    /// Checks that there exists at least one mined cell which is open.
     ///
-    /// This is effectively the loss-condition for the game.
+    /// This is effectively the loss-condition for the game, unless the rules give the player several lives.
     pub fn check_open_mines_exist(&self) -> bool {
-        self.grid
-            .iter()
-            .flatten()
-            .any(|cell| cell.is_open() && cell.is_mined())
+        self.cells_amounts.exploded_amount > 0
+    }
+
+    /// The method returns the number of the exploded (open mined) cells. The game is lost once there are as many of
+    /// them as [`Rules::lives_amount`].
+    pub fn get_exploded_cells_amount(&self) -> u32 {
+        self.cells_amounts.exploded_amount
     }
 
     /// Checks that all the empty cells are open.
     ///
     /// This is effectively the win-condition for the game.
     pub fn check_all_non_mines_open(&self) -> bool {
-        self.grid
-            .iter()
-            .flatten()
-            .filter(|cell| !cell.is_mined())
-            .all(|cell| cell.is_open())
+        let CellsAmounts {
+            mined_amount,
+            open_amount,
+            exploded_amount,
+            ..
+        } = self.cells_amounts;
+
+        open_amount - exploded_amount == self.cells.len() as u32 - mined_amount
     }
 
     /// Opens all the yet-not-flagged cells with mines.
     ///
     /// The method should be called when the game is already lost to reveal the real positions of mines.
     pub fn open_missed_mines(&mut self) {
-        self.grid
-            .iter_mut()
-            .flatten()
-            .filter(|cell| cell.is_mined() && !cell.is_flagged())
-            .for_each(|cell| {
-                cell.open();
-            });
+        let missed_mines_positions = self
+            .get_positions()
+            .filter(|position| {
+                self.get_cell(*position)
+                    .is_some_and(|cell| cell.is_mined() && !cell.is_flagged() && !cell.is_open())
+            })
+            .collect::<Vec<Position>>();
+
+        missed_mines_positions.into_iter().for_each(|position| {
+            self.update_cell(position, Cell::open);
+        });
+    }
+}
+
+impl Debug for Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.get_rows() {
+            for cell in row {
+                write!(f, "{:?}", cell)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        write!(f, "")
+    }
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for i in 0..self.columns_amount {
+            write!(
+                f,
+                "{:^3}",
+                if i == 0 {
+                    "   0 ".to_string()
+                } else {
+                    i.to_string()
+                }
+            )?;
+        }
+
+        writeln!(f)?;
+
+        for (i, row) in self.get_rows().enumerate() {
+            write!(f, "{:^3}", i)?;
+
+            // the odd rows of the hexagonal grid are shifted to the right by half a cell
+            if self.rules.grid == Grid::Hexagonal && i % 2 == 1 {
+                write!(f, " ")?;
+            }
+
+            for cell in row {
+                write!(f, "{} ", cell)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        write!(f, "")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Cell, CellsAmounts, Field, FieldError, Position, SerializedField};
+    use crate::rules::{FirstClickPolicy, GenerationMode, Grid, Neighborhood, Rules, Topology};
+
+    #[test]
+    fn create_field_instance_correct_params() {
+        let field = Field::new(3, 3, 3, Rules::default(), Some(42));
+        assert!(field.is_ok());
+
+        assert_eq!(
+            field.unwrap(),
+            Field::try_from(SerializedField {
+                grid: vec![
+                    vec![Cell::new(), Cell::new(), Cell::new(),],
+                    vec![Cell::new(), Cell::new(), Cell::new(),],
+                    vec![Cell::new(), Cell::new(), Cell::new(),],
+                ],
+                mines_amount: 3,
+                rules: Rules::default(),
+                seed: 42,
+            })
+            .unwrap()
+        )
+    }
+
+    #[test]
+    fn create_field_fails_when_not_enough_cells() {
+        let field = Field::new(1, 1, 1, Rules::default(), None);
+        assert!(field.is_err_and(|err| err == FieldError::NotEnoughCells));
+    }
+
+    #[test]
+    fn create_field_fails_when_not_enough_mines() {
+        let field = Field::new(3, 3, 0, Rules::default(), None);
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(8)));
+    }
+
+    #[test]
+    fn create_field_fails_when_too_many_mines() {
+        let field = Field::new(3, 3, 9, Rules::default(), None);
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(8)));
+    }
+
+    #[test]
+    fn create_field_fails_when_too_many_mines_for_no_guess_generation() {
+        let rules = Rules {
+            generation: GenerationMode::NoGuess,
+            ..Default::default()
+        };
+
+        let field = Field::new(4, 4, 8, rules, None);
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(7)));
+
+        let field = Field::new(2, 5, 5, rules, None);
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(4)));
+    }
+
+    #[test]
+    fn create_field_accounts_for_the_first_click_policy() {
+        let rules = |first_click| Rules {
+            first_click,
+            ..Default::default()
+        };
+
+        let field = Field::new(3, 3, 9, rules(FirstClickPolicy::NoProtection), None);
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(8)));
+
+        let field = Field::new(5, 5, 17, rules(FirstClickPolicy::Opening), None);
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(16)));
+
+        let field = Field::new(6, 9, 30, rules(FirstClickPolicy::SafeArea(2)), None);
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(29)));
+
+        let field = Field::new(3, 20, 46, rules(FirstClickPolicy::SafeArea(2)), None);
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(45)));
+    }
+
+    #[test]
+    fn create_field_from_mine_positions() {
+        let field = Field::from_mine_positions(
+            3,
+            3,
+            &[
+                Position::new(0, 0),
+                Position::new(0, 1),
+                Position::new(1, 2),
+            ],
+            Rules::default(),
+        );
+        assert!(field.is_ok());
+
+        let mut expected_field = create_stub_mined_field(false);
+        expected_field.update_mines_around_values();
+
+        assert_eq!(field.unwrap(), expected_field);
+    }
+
+    #[test]
+    fn create_field_from_mine_positions_fails_on_invalid_positions() {
+        let field = Field::from_mine_positions(
+            3,
+            3,
+            &[Position::new(0, 0), Position::new(3, 1)],
+            Rules::default(),
+        );
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinePosition(Position::new(3, 1))));
+
+        let field = Field::from_mine_positions(
+            3,
+            3,
+            &[
+                Position::new(0, 0),
+                Position::new(2, 1),
+                Position::new(0, 0),
+            ],
+            Rules::default(),
+        );
+        assert!(
+            field.is_err_and(|err| err == FieldError::DuplicateMinePosition(Position::new(0, 0)))
+        );
+    }
+
+    #[test]
+    fn create_field_from_mine_positions_fails_on_invalid_mines_amount() {
+        let field = Field::from_mine_positions(2, 2, &[], Rules::default());
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(3)));
+
+        let field = Field::from_mine_positions(
+            2,
+            2,
+            &[
+                Position::new(0, 0),
+                Position::new(0, 1),
+                Position::new(1, 0),
+                Position::new(1, 1),
+            ],
+            Rules::default(),
+        );
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(3)));
+
+        let field = Field::from_mine_positions(1, 1, &[Position::new(0, 0)], Rules::default());
+        assert!(field.is_err_and(|err| err == FieldError::NotEnoughCells));
+    }
+
+    #[test]
+    fn the_field_gets_correctly_populated_with_mines() {
+        let mut field = Field::new(3, 3, 3, Rules::default(), None).unwrap();
+        let result = field.populate_with_mines(None);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            field.mines_amount,
+            field
+                .cells
+                .iter()
+                .filter(|cell| cell.is_mined())
+                .collect::<Vec<&Cell>>()
+                .len() as u32
+        );
+    }
+
+    #[test]
+    fn populate_with_mines_correctly_excepts_a_cell() {
+        for _ in 0..100 {
+            let mut field = Field::new(3, 3, 3, Rules::default(), None).unwrap();
+            let result = field.populate_with_mines(Some(Position::new(0, 0)));
+
+            assert!(result.is_ok());
+            assert!(!field.cells[0].is_mined())
+        }
+    }
+
+    #[test]
+    fn populate_with_mines_is_reproducible_with_the_same_seed() {
+        let mines_positions = |field: &Field| {
+            field
+                .get_positions()
+                .filter(|position| field.get_cell(*position).unwrap().is_mined())
+                .collect::<Vec<Position>>()
+        };
+
+        let mut field = Field::new(16, 30, 99, Rules::default(), Some(42)).unwrap();
+        field
+            .populate_with_mines(Some(Position::new(5, 5)))
+            .unwrap();
+
+        for _ in 0..10 {
+            let mut same_field = Field::new(16, 30, 99, Rules::default(), Some(42)).unwrap();
+            same_field
+                .populate_with_mines(Some(Position::new(5, 5)))
+                .unwrap();
+
+            assert_eq!(mines_positions(&field), mines_positions(&same_field));
+        }
+
+        let mut other_field = Field::new(16, 30, 99, Rules::default(), Some(43)).unwrap();
+        other_field
+            .populate_with_mines(Some(Position::new(5, 5)))
+            .unwrap();
+
+        assert_ne!(mines_positions(&field), mines_positions(&other_field));
+    }
+
+    #[test]
+    fn no_guess_generation_produces_fields_solvable_from_the_first_cell() {
+        let rules = Rules {
+            generation: GenerationMode::NoGuess,
+            ..Default::default()
+        };
+
+        for seed in 0..5 {
+            let mut field = Field::new(16, 16, 40, rules, Some(seed)).unwrap();
+            field
+                .populate_with_mines(Some(Position::new(3, 7)))
+                .unwrap();
+
+            assert_eq!(
+                field
+                    .get_cell(Position::new(3, 7))
+                    .unwrap()
+                    .get_mines_around_amount(),
+                Some(0)
+            );
+            assert!(field
+                .clone()
+                .solve_from(Position::new(3, 7))
+                .check_all_non_mines_open());
+            assert_eq!(
+                field.cells.iter().filter(|cell| cell.is_mined()).count(),
+                40
+            );
+        }
+    }
+
+    #[test]
+    fn no_guess_generation_supports_several_mines_per_cell() {
+        let rules = Rules {
+            generation: GenerationMode::NoGuess,
+            max_mines_per_cell: 2,
+            ..Default::default()
+        };
+
+        for seed in 0..3 {
+            let mut field = Field::new(12, 12, 30, rules, Some(seed)).unwrap();
+            field
+                .populate_with_mines(Some(Position::new(5, 5)))
+                .unwrap();
+
+            assert!(field
+                .clone()
+                .solve_from(Position::new(5, 5))
+                .check_all_non_mines_open());
+            assert_eq!(
+                field
+                    .cells
+                    .iter()
+                    .map(|cell| cell.get_mines_amount() as u32)
+                    .sum::<u32>(),
+                30
+            );
+        }
+    }
+
+    #[test]
+    fn no_guess_generation_supports_anti_mines() {
+        let rules = Rules {
+            generation: GenerationMode::NoGuess,
+            anti_mines_amount: 3,
+            ..Default::default()
+        };
+
+        for seed in 0..3 {
+            let mut field = Field::new(9, 9, 10, rules, Some(seed)).unwrap();
+            field
+                .populate_with_mines(Some(Position::new(4, 4)))
+                .unwrap();
+
+            assert!(field
+                .clone()
+                .solve_from(Position::new(4, 4))
+                .check_all_non_mines_open());
+            assert_eq!(
+                field
+                    .cells
+                    .iter()
+                    .filter(|cell| cell.is_anti_mined())
+                    .count(),
+                3
+            );
+        }
+    }
+
+    #[test]
+    fn no_guess_generation_is_reproducible_with_the_same_seed() {
+        let rules = Rules {
+            generation: GenerationMode::NoGuess,
+            ..Default::default()
+        };
+
+        let mut field = Field::new(9, 9, 10, rules, Some(42)).unwrap();
+        field
+            .populate_with_mines(Some(Position::new(0, 0)))
+            .unwrap();
+
+        let mut same_field = Field::new(9, 9, 10, rules, Some(42)).unwrap();
+        same_field
+            .populate_with_mines(Some(Position::new(0, 0)))
+            .unwrap();
+
+        assert_eq!(field, same_field);
+    }
+
+    #[test]
+    fn huge_fields_are_populated_with_mines() {
+        let mut field = Field::new(300, 300, 20_000, Rules::default(), Some(42)).unwrap();
+        field
+            .populate_with_mines(Some(Position::new(299, 299)))
+            .unwrap();
+
+        assert_eq!(field.get_size(), (300, 300, 90_000));
+        assert!(!field.get_cell(Position::new(299, 299)).unwrap().is_mined());
+        assert_eq!(
+            field.cells.iter().filter(|cell| cell.is_mined()).count(),
+            20_000
+        );
+    }
+
+    #[test]
+    fn the_mines_beyond_the_255th_row_and_column_are_counted() {
+        let field = Field::from_mine_positions(
+            300,
+            300,
+            &[Position::new(255, 255), Position::new(256, 256)],
+            Rules::default(),
+        )
+        .unwrap();
+
+        let mines_around_amount =
+            |position| field.get_cell(position).unwrap().get_mines_around_amount();
+        assert_eq!(mines_around_amount(Position::new(255, 256)), Some(2));
+        assert_eq!(mines_around_amount(Position::new(257, 257)), Some(1));
+        assert_eq!(mines_around_amount(Position::new(0, 0)), Some(0));
+        assert!(field.get_cell(Position::new(300, 0)).is_none());
+    }
+
+    #[test]
+    fn get_seed_returns_the_provided_seed() {
+        let field = Field::new(3, 3, 3, Rules::default(), Some(42)).unwrap();
+        assert_eq!(field.get_seed(), 42);
+    }
+
+    #[test]
+    fn populate_with_mines_keeps_the_safe_area_free_of_mines() {
+        let rules = |first_click| Rules {
+            first_click,
+            ..Default::default()
+        };
+
+        for seed in 0..20 {
+            let mut field =
+                Field::new(9, 9, 56, rules(FirstClickPolicy::Opening), Some(seed)).unwrap();
+            field
+                .populate_with_mines(Some(Position::new(0, 4)))
+                .unwrap();
+
+            assert_eq!(
+                field
+                    .get_cell(Position::new(0, 4))
+                    .unwrap()
+                    .get_mines_around_amount(),
+                Some(0)
+            );
+
+            let mut field =
+                Field::new(9, 9, 56, rules(FirstClickPolicy::SafeArea(2)), Some(seed)).unwrap();
+            field
+                .populate_with_mines(Some(Position::new(4, 4)))
+                .unwrap();
+
+            assert!(field
+                .get_area_positions(Position::new(4, 4), 2)
+                .into_iter()
+                .all(|position| !field.get_cell(position).unwrap().is_mined()));
+        }
+    }
+
+    #[test]
+    fn populate_with_mines_fails_on_invalid_excepted_cell_position() {
+        let mut field = Field::new(3, 3, 3, Rules::default(), None).unwrap();
+        let result = field.populate_with_mines(Some(Position::new(5, 5)));
+
+        assert!(result
+            .is_err_and(|err| err == FieldError::InvalidExceptedCellPosition(Position::new(5, 5))));
+    }
+
+    #[test]
+    fn populate_with_mines_fails_when_there_are_mines_already() {
+        let mut field = Field::new(3, 3, 3, Rules::default(), None).unwrap();
+        field.populate_with_mines(None).unwrap();
+        let result = field.populate_with_mines(None);
+
+        assert!(result.is_err_and(|err| err == FieldError::MinesAlreadyExist));
+    }
+
+    #[test]
+    fn mines_around_values_get_updated_correctly() {
+        let mut field = create_stub_mined_field(false);
+        field.update_mines_around_values();
+
+        let result = field
+            .cells
+            .iter()
+            .map(|cell| cell.get_mines_around_amount())
+            .collect::<Vec<Option<i16>>>();
+
+        assert_eq!(
+            result,
+            [
+                None,
+                None,
+                Some(2),
+                Some(2),
+                Some(3),
+                None,
+                Some(0),
+                Some(1),
+                Some(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn get_size_correctly_calculates_dimensions() {
+        let field = Field::new(3, 3, 3, Rules::default(), None).unwrap();
+        let size = field.get_size();
+
+        assert_eq!(size, (3, 3, 9));
+    }
+
+    #[test]
+    fn get_cell_correctly_finds_the_cell_by_its_position() {
+        let field = Field::new(3, 3, 3, Rules::default(), None).unwrap();
+        let cell = field.get_cell(Position::new(0, 0));
+
+        assert!(cell.is_some());
+        assert_eq!(cell.unwrap(), &field.cells[0])
+    }
+
+    #[test]
+    fn get_cell_returns_none_for_non_existing_cells() {
+        let field = Field::new(3, 3, 3, Rules::default(), None).unwrap();
+        let cell = field.get_cell(Position::new(10, 10));
+
+        assert!(cell.is_none());
+    }
+
+    #[test]
+    fn update_cell_changes_the_cell_by_its_position() {
+        let mut field = Field::new(3, 3, 3, Rules::default(), None).unwrap();
+        let result = field.update_cell(Position::new(1, 2), Cell::open);
+
+        assert!(result.is_some());
+        assert!(field.cells[5].is_open());
+    }
+
+    #[test]
+    fn update_cell_returns_none_for_non_existing_cells() {
+        let mut field = Field::new(3, 3, 3, Rules::default(), None).unwrap();
+        let result = field.update_cell(Position::new(10, 10), Cell::open);
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn get_adjacent_positions_for_middle_cell() {
+        let field = Field::new(20, 20, 3, Rules::default(), None).unwrap();
+
+        assert_eq!(
+            field.get_adjacent_positions(Position::new(10, 10)),
+            [
+                Position::new(9, 9),
+                Position::new(9, 10),
+                Position::new(9, 11),
+                Position::new(10, 9),
+                Position::new(10, 11),
+                Position::new(11, 9),
+                Position::new(11, 10),
+                Position::new(11, 11)
+            ]
+        );
+    }
+
+    #[test]
+    fn get_adjacent_positions_for_edge_cells() {
+        let field = Field::new(20, 20, 3, Rules::default(), None).unwrap();
+
+        assert_eq!(
+            field.get_adjacent_positions(Position::new(0, 10)),
+            [
+                Position::new(0, 9),
+                Position::new(0, 11),
+                Position::new(1, 9),
+                Position::new(1, 10),
+                Position::new(1, 11)
+            ]
+        );
+        assert_eq!(
+            field.get_adjacent_positions(Position::new(19, 19)),
+            [
+                Position::new(18, 18),
+                Position::new(18, 19),
+                Position::new(19, 18)
+            ]
+        );
+        assert!(field
+            .get_adjacent_positions(Position::new(20, 20))
+            .is_empty());
+    }
+
+    #[test]
+    fn get_adjacent_positions_wraps_around_on_a_torus() {
+        let rules = Rules {
+            topology: Topology::Torus,
+            ..Default::default()
+        };
+        let field = Field::new(20, 30, 3, rules, None).unwrap();
+
+        assert_eq!(
+            field.get_adjacent_positions(Position::new(0, 0)),
+            [
+                Position::new(0, 1),
+                Position::new(0, 29),
+                Position::new(1, 0),
+                Position::new(1, 1),
+                Position::new(1, 29),
+                Position::new(19, 0),
+                Position::new(19, 1),
+                Position::new(19, 29)
+            ]
+        );
+
+        // Every cell has exactly 8 neighbours, but on a tiny torus some of them coincide.
+        let field = Field::new(2, 3, 1, rules, None).unwrap();
+        assert_eq!(
+            field.get_adjacent_positions(Position::new(0, 0)),
+            [
+                Position::new(0, 1),
+                Position::new(0, 2),
+                Position::new(1, 0),
+                Position::new(1, 1),
+                Position::new(1, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn the_mines_are_counted_across_the_edges_on_a_torus() {
+        let rules = Rules {
+            topology: Topology::Torus,
+            ..Default::default()
+        };
+        let mut field = Field::from_mine_positions(4, 5, &[Position::new(0, 0)], rules).unwrap();
+
+        for position in [
+            Position::new(3, 4),
+            Position::new(0, 4),
+            Position::new(3, 0),
+            Position::new(1, 1),
+        ] {
+            assert_eq!(
+                field.get_cell(position).unwrap().get_mines_around_amount(),
+                Some(1)
+            );
+        }
+        assert_eq!(
+            field
+                .get_cell(Position::new(2, 2))
+                .unwrap()
+                .get_mines_around_amount(),
+            Some(0)
+        );
+
+        // The only opening spreads across the edges and reveals every cell but the mine.
+        field.open_cell(Position::new(2, 2));
+        assert!(field.check_all_non_mines_open());
+    }
+
+    #[test]
+    fn the_safe_area_wraps_around_on_a_torus() {
+        let rules = Rules {
+            first_click: FirstClickPolicy::Opening,
+            topology: Topology::Torus,
+            ..Default::default()
+        };
+
+        for seed in 0..20 {
+            let mut field = Field::new(6, 6, 27, rules, Some(seed)).unwrap();
+            field
+                .populate_with_mines(Some(Position::new(0, 0)))
+                .unwrap();
+
+            assert_eq!(
+                field
+                    .get_cell(Position::new(0, 0))
+                    .unwrap()
+                    .get_mines_around_amount(),
+                Some(0)
+            );
+        }
+    }
+
+    #[test]
+    fn get_adjacent_positions_on_a_hexagonal_grid() {
+        let rules = Rules {
+            grid: Grid::Hexagonal,
+            ..Default::default()
+        };
+        let field = Field::new(20, 20, 3, rules, None).unwrap();
+
+        // The rows above and below an even row are shifted to the right, so the neighbours there lean to the left...
+        assert_eq!(
+            field.get_adjacent_positions(Position::new(10, 10)),
+            [
+                Position::new(9, 9),
+                Position::new(9, 10),
+                Position::new(10, 9),
+                Position::new(10, 11),
+                Position::new(11, 9),
+                Position::new(11, 10)
+            ]
+        );
+        // ...and vice versa.
+        assert_eq!(
+            field.get_adjacent_positions(Position::new(11, 10)),
+            [
+                Position::new(10, 10),
+                Position::new(10, 11),
+                Position::new(11, 9),
+                Position::new(11, 11),
+                Position::new(12, 10),
+                Position::new(12, 11)
+            ]
+        );
+        assert_eq!(
+            field.get_adjacent_positions(Position::new(0, 0)),
+            [Position::new(0, 1), Position::new(1, 0)]
+        );
     }
-}
 
-impl Debug for Field {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in self.grid.iter() {
-            for cell in row {
-                write!(f, "{:?}", cell)?;
+    #[test]
+    fn the_cells_of_a_hexagonal_torus_are_adjacent_to_each_other_both_ways() {
+        let rules = Rules {
+            grid: Grid::Hexagonal,
+            topology: Topology::Torus,
+            ..Default::default()
+        };
+        let field = Field::new(6, 5, 3, rules, None).unwrap();
+
+        for position in field.get_positions() {
+            let adjacent_positions = field.get_adjacent_positions(position);
+            assert_eq!(adjacent_positions.len(), 6);
+
+            for adjacent_position in adjacent_positions {
+                assert!(field
+                    .get_adjacent_positions(adjacent_position)
+                    .contains(&position));
             }
-
-            writeln!(f)?;
         }
+    }
 
-        write!(f, "")
+    #[test]
+    fn create_field_fails_on_a_hexagonal_torus_with_odd_rows_amount() {
+        let rules = Rules {
+            grid: Grid::Hexagonal,
+            topology: Topology::Torus,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Field::new(5, 6, 3, rules, None),
+            Err(FieldError::OddRowsAmountOnHexagonalTorus)
+        );
+        assert_eq!(
+            Field::from_mine_positions(5, 6, &[Position::new(0, 0)], rules),
+            Err(FieldError::OddRowsAmountOnHexagonalTorus)
+        );
+        // Either the plane or the even number of rows is fine.
+        assert!(Field::new(6, 5, 3, rules, None).is_ok());
+        assert!(Field::new(
+            5,
+            6,
+            3,
+            Rules {
+                grid: Grid::Hexagonal,
+                ..Default::default()
+            },
+            None
+        )
+        .is_ok());
     }
-}
 
-impl Display for Field {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for (i, _) in self.grid[0].iter().enumerate() {
-            write!(
-                f,
-                "{:^3}",
-                if i == 0 {
-                    "   0 ".to_string()
-                } else {
-                    i.to_string()
-                }
-            )?;
-        }
+    #[test]
+    fn the_safe_area_is_a_hexagon_on_a_hexagonal_grid() {
+        let rules = Rules {
+            first_click: FirstClickPolicy::SafeArea(2),
+            grid: Grid::Hexagonal,
+            ..Default::default()
+        };
+
+        // 1 + 6 + 12 cells are kept free of mines, the rest of the 10x10 field is mined.
+        assert_eq!(
+            Field::new(10, 10, 82, rules, None),
+            Err(FieldError::InvalidMinesAmount(81))
+        );
 
-        writeln!(f)?;
+        let mut field = Field::new(10, 10, 81, rules, Some(0)).unwrap();
+        field
+            .populate_with_mines(Some(Position::new(5, 5)))
+            .unwrap();
+
+        let safe_positions = field
+            .get_positions()
+            .filter(|position| !field.get_cell(*position).unwrap().is_mined())
+            .collect::<Vec<Position>>();
+        assert_eq!(safe_positions.len(), 19);
+        assert!(safe_positions.contains(&Position::new(3, 4)));
+        assert!(safe_positions.contains(&Position::new(3, 6)));
+        assert!(!safe_positions.contains(&Position::new(3, 3)));
+        assert!(!safe_positions.contains(&Position::new(3, 7)));
+    }
 
-        for (i, row) in self.grid.iter().enumerate() {
-            write!(f, "{:^3}", i)?;
+    #[test]
+    fn get_adjacent_positions_follows_the_neighborhood() {
+        let rules = |neighborhood| Rules {
+            neighborhood,
+            ..Default::default()
+        };
 
-            for cell in row {
-                write!(f, "{} ", cell)?;
-            }
+        let field = Field::new(20, 20, 3, rules(Neighborhood::Orthogonal), None).unwrap();
+        assert_eq!(
+            field.get_adjacent_positions(Position::new(10, 10)),
+            [
+                Position::new(9, 10),
+                Position::new(10, 9),
+                Position::new(10, 11),
+                Position::new(11, 10)
+            ]
+        );
 
-            writeln!(f)?;
-        }
+        let field = Field::new(20, 20, 3, rules(Neighborhood::KnightMove), None).unwrap();
+        assert_eq!(
+            field.get_adjacent_positions(Position::new(1, 1)),
+            [
+                Position::new(0, 3),
+                Position::new(2, 3),
+                Position::new(3, 0),
+                Position::new(3, 2)
+            ]
+        );
 
-        write!(f, "")
+        let field = Field::new(20, 20, 3, rules(Neighborhood::Extended), None).unwrap();
+        assert_eq!(
+            field.get_adjacent_positions(Position::new(10, 10)).len(),
+            24
+        );
+        assert_eq!(field.get_adjacent_positions(Position::new(0, 0)).len(), 8);
+
+        let field = Field::new(
+            20,
+            20,
+            3,
+            Rules {
+                grid: Grid::Hexagonal,
+                neighborhood: Neighborhood::Extended,
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            field.get_adjacent_positions(Position::new(10, 10)).len(),
+            18
+        );
+        assert_eq!(
+            field.get_adjacent_positions(Position::new(11, 10)).len(),
+            18
+        );
     }
-}
-
-#[cfg(test)]
-mod test {
-    use super::{Cell, Field, FieldError};
 
     #[test]
-    fn create_field_instance_correct_params() {
-        let field = Field::new(3, 3, 3);
-        assert!(field.is_ok());
+    fn create_field_fails_when_the_neighborhood_is_not_defined_for_the_grid() {
+        let rules = Rules {
+            grid: Grid::Hexagonal,
+            neighborhood: Neighborhood::KnightMove,
+            ..Default::default()
+        };
 
         assert_eq!(
-            field.unwrap(),
-            Field {
-                grid: vec![
-                    vec![Cell::new((0, 0)), Cell::new((0, 1)), Cell::new((0, 2)),],
-                    vec![Cell::new((1, 0)), Cell::new((1, 1)), Cell::new((1, 2)),],
-                    vec![Cell::new((2, 0)), Cell::new((2, 1)), Cell::new((2, 2)),],
-                ],
-                mines_amount: 3
-            }
-        )
+            Field::new(10, 10, 3, rules, None),
+            Err(FieldError::UnsupportedNeighborhood)
+        );
+        assert_eq!(
+            Field::from_mine_positions(10, 10, &[Position::new(0, 0)], rules),
+            Err(FieldError::UnsupportedNeighborhood)
+        );
     }
 
     #[test]
-    fn create_field_fails_when_not_enough_cells() {
-        let field = Field::new(1, 1, 1);
-        assert!(field.is_err_and(|err| err == FieldError::NotEnoughCells));
+    fn create_field_accounts_for_the_neighborhood_of_the_first_click() {
+        let rules = |neighborhood| Rules {
+            first_click: FirstClickPolicy::Opening,
+            neighborhood,
+            ..Default::default()
+        };
+
+        // The cell and its 4 orthogonal neighbours are kept free of mines.
+        let field = Field::new(5, 5, 21, rules(Neighborhood::Orthogonal), None);
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(20)));
+
+        // The whole 5x5 square is kept free of mines, and there's no room left for a mine.
+        let field = Field::new(5, 5, 1, rules(Neighborhood::Extended), None);
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(0)));
+
+        let mut field = Field::new(7, 7, 40, rules(Neighborhood::KnightMove), Some(0)).unwrap();
+        field
+            .populate_with_mines(Some(Position::new(3, 3)))
+            .unwrap();
+        assert_eq!(
+            field
+                .get_cell(Position::new(3, 3))
+                .unwrap()
+                .get_mines_around_amount(),
+            Some(0)
+        );
     }
 
     #[test]
-    fn create_field_fails_when_not_enough_mines() {
-        let field = Field::new(3, 3, 0);
-        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(8)));
+    fn the_numbers_count_the_mines_of_the_neighborhood() {
+        let rules = Rules {
+            neighborhood: Neighborhood::Extended,
+            ..Default::default()
+        };
+        let mines_positions = (0..5)
+            .flat_map(|row_index| {
+                (0..5).map(move |column_index| Position::new(row_index, column_index))
+            })
+            .filter(|position| *position != Position::new(2, 2))
+            .collect::<Vec<Position>>();
+        let field = Field::from_mine_positions(6, 6, &mines_positions, rules).unwrap();
+
+        assert_eq!(
+            field
+                .get_cell(Position::new(2, 2))
+                .unwrap()
+                .get_mines_around_amount(),
+            Some(24)
+        );
+        assert_eq!(
+            field
+                .get_cell(Position::new(5, 5))
+                .unwrap()
+                .get_mines_around_amount(),
+            Some(4)
+        );
     }
 
     #[test]
-    fn create_field_fails_when_too_many_mines() {
-        let field = Field::new(3, 3, 9);
-        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(8)));
+    fn open_surrounding_cells_follows_the_neighborhood() {
+        let rules = Rules {
+            neighborhood: Neighborhood::KnightMove,
+            ..Default::default()
+        };
+        let mut field = Field::from_mine_positions(5, 5, &[Position::new(0, 1)], rules).unwrap();
+
+        assert_eq!(field.open_cell(Position::new(2, 2)), [Position::new(2, 2)]);
+        field.toggle_cell_flag(Position::new(0, 1));
+
+        // The knight's move cells get opened, and the openings among them spread further.
+        let opened_cells_positions = field.open_surrounding_cells(Position::new(2, 2));
+        assert!(opened_cells_positions.contains(&Position::new(0, 3)));
+        assert!(opened_cells_positions.contains(&Position::new(4, 1)));
+        assert!(field.check_all_non_mines_open());
     }
 
     #[test]
-    fn the_field_gets_correctly_populated_with_mines() {
-        let mut field = Field::new(3, 3, 3).unwrap();
-        let result = field.populate_with_mines(None);
+    fn create_field_allows_for_several_mines_per_cell() {
+        let rules = |max_mines_per_cell| Rules {
+            max_mines_per_cell,
+            ..Default::default()
+        };
+
+        // All the cells but the first opened one might hold up to 3 mines each.
+        let field = Field::new(3, 3, 25, rules(3), None);
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(24)));
+        assert!(Field::new(3, 3, 24, rules(3), None).is_ok());
 
-        assert!(result.is_ok());
         assert_eq!(
-            field.mines_amount,
-            field
-                .grid
-                .iter()
-                .flatten()
-                .filter(|cell| cell.is_mined())
-                .collect::<Vec<&Cell>>()
-                .len() as u16
+            Field::new(3, 3, 3, rules(0), None),
+            Err(FieldError::InvalidMaxMinesPerCell)
+        );
+        assert_eq!(
+            Field::from_mine_positions(3, 3, &[Position::new(0, 0)], rules(10)),
+            Err(FieldError::InvalidMaxMinesPerCell)
         );
     }
 
     #[test]
-    fn populate_with_mines_correctly_excepts_a_cell() {
-        for _ in 0..100 {
-            let mut field = Field::new(3, 3, 3).unwrap();
-            let result = field.populate_with_mines(Some((0, 0)));
+    fn create_field_fails_without_lives() {
+        let rules = Rules {
+            lives_amount: 0,
+            ..Default::default()
+        };
 
-            assert!(result.is_ok());
-            assert!(!field.grid[0][0].is_mined())
-        }
+        assert_eq!(
+            Field::new(3, 3, 3, rules, None),
+            Err(FieldError::InvalidLivesAmount)
+        );
+        assert_eq!(
+            Field::from_mine_positions(3, 3, &[Position::new(0, 0)], rules),
+            Err(FieldError::InvalidLivesAmount)
+        );
     }
 
     #[test]
-    fn populate_with_mines_fails_on_invalid_excepted_cell_position() {
-        let mut field = Field::new(3, 3, 3).unwrap();
-        let result = field.populate_with_mines(Some((5, 5)));
+    fn populate_with_mines_puts_several_mines_into_the_cells() {
+        let rules = Rules {
+            max_mines_per_cell: 3,
+            ..Default::default()
+        };
+        let mut field = Field::new(4, 4, 40, rules, Some(7)).unwrap();
+        field
+            .populate_with_mines(Some(Position::new(0, 0)))
+            .unwrap();
+
+        let mines_amounts = field
+            .cells
+            .iter()
+            .map(|cell| cell.get_mines_amount() as u32)
+            .collect::<Vec<u32>>();
 
-        assert!(result.is_err_and(|err| err == FieldError::InvalidExceptedCellPosition((5, 5))));
+        assert_eq!(mines_amounts.iter().sum::<u32>(), 40);
+        assert_eq!(mines_amounts[0], 0);
+        assert!(mines_amounts.iter().all(|mines_amount| *mines_amount <= 3));
+        assert!(mines_amounts.iter().any(|mines_amount| *mines_amount > 1));
     }
 
     #[test]
-    fn populate_with_mines_fails_when_there_are_mines_already() {
-        let mut field = Field::new(3, 3, 3).unwrap();
-        field.populate_with_mines(None).unwrap();
-        let result = field.populate_with_mines(None);
+    fn the_numbers_count_the_mines_rather_than_the_mined_cells() {
+        let rules = Rules {
+            max_mines_per_cell: 3,
+            ..Default::default()
+        };
+        let field = Field::from_mine_positions(
+            2,
+            3,
+            &[
+                Position::new(0, 0),
+                Position::new(0, 0),
+                Position::new(0, 2),
+                Position::new(0, 0),
+            ],
+            rules,
+        )
+        .unwrap();
 
-        assert!(result.is_err_and(|err| err == FieldError::MinesAlreadyExist));
+        assert_eq!(field.get_mines_amount(), 4);
+        assert_eq!(
+            field
+                .get_cell(Position::new(0, 0))
+                .unwrap()
+                .get_mines_amount(),
+            3
+        );
+        assert_eq!(
+            field
+                .get_cell(Position::new(1, 1))
+                .unwrap()
+                .get_mines_around_amount(),
+            Some(4)
+        );
+        assert_eq!(
+            field
+                .get_cell(Position::new(1, 0))
+                .unwrap()
+                .get_mines_around_amount(),
+            Some(3)
+        );
+
+        // A cell can't hold more mines than the rules allow for.
+        assert_eq!(
+            Field::from_mine_positions(2, 3, &[Position::new(0, 0); 4], rules),
+            Err(FieldError::DuplicateMinePosition(Position::new(0, 0)))
+        );
     }
 
     #[test]
-    fn mines_around_values_get_updated_correctly() {
-        let mut field = create_stub_mined_field(false);
-        field.update_mines_around_values();
+    fn the_flags_cycle_up_to_the_maximum_number_of_mines_per_cell() {
+        let rules = Rules {
+            max_mines_per_cell: 2,
+            ..Default::default()
+        };
+        let mut field = Field::from_mine_positions(
+            2,
+            2,
+            &[
+                Position::new(0, 0),
+                Position::new(0, 0),
+                Position::new(0, 1),
+            ],
+            rules,
+        )
+        .unwrap();
 
-        let result = field
-            .grid
-            .iter()
-            .flatten()
-            .map(|cell| cell.get_mines_around_amount())
-            .collect::<Vec<Option<u8>>>();
+        field.toggle_cell_flag(Position::new(0, 0));
+        field.toggle_cell_flag(Position::new(0, 0));
+        field.toggle_cell_flag(Position::new(0, 1));
+        assert_eq!(field.get_flagged_cells_amount(), 3);
+
+        // The number is only satisfied once there are as many flags around it as there are mines.
+        field.open_cell(Position::new(1, 0));
+        assert!(field
+            .open_surrounding_cells(Position::new(1, 0))
+            .contains(&Position::new(1, 1)));
+        assert!(field.check_all_non_mines_open());
 
+        // The third flag removes all of them.
+        field.toggle_cell_flag(Position::new(0, 0));
+        assert_eq!(field.get_flagged_cells_amount(), 1);
+        assert_eq!(field.cells_amounts, CellsAmounts::of(&field.cells));
+    }
+
+    #[test]
+    fn create_field_leaves_room_for_the_anti_mines() {
+        let rules = |anti_mines_amount| Rules {
+            anti_mines_amount,
+            ..Default::default()
+        };
+
+        // Apart from the first opened cell, there must be at least one cell left for the mines.
+        let field = Field::new(3, 3, 1, rules(8), None);
+        assert!(field.is_err_and(|err| err == FieldError::InvalidAntiMinesAmount(7)));
+        assert!(Field::new(3, 3, 1, rules(7), None).is_ok());
+
+        // The anti-mines take up the cells the mines might've been put into.
+        let field = Field::new(3, 3, 3, rules(6), None);
+        assert!(field.is_err_and(|err| err == FieldError::InvalidMinesAmount(2)));
+
+        // A cell can't hold both a mine and an anti-mine.
         assert_eq!(
-            result,
-            [
-                None,
-                None,
-                Some(2),
-                Some(2),
-                Some(3),
-                None,
-                Some(0),
-                Some(1),
-                Some(1)
-            ]
+            Field::from_mine_and_anti_mine_positions(
+                3,
+                3,
+                &[Position::new(0, 0)],
+                &[Position::new(0, 0)],
+                Rules::default()
+            ),
+            Err(FieldError::DuplicateMinePosition(Position::new(0, 0)))
         );
     }
 
     #[test]
-    fn get_size_correctly_calculates_dimensions() {
-        let field = Field::new(3, 3, 3).unwrap();
-        let size = field.get_size();
+    fn populate_with_mines_puts_the_anti_mines() {
+        let rules = Rules {
+            anti_mines_amount: 4,
+            ..Default::default()
+        };
+        let mut field = Field::new(5, 5, 6, rules, Some(7)).unwrap();
+        field
+            .populate_with_mines(Some(Position::new(0, 0)))
+            .unwrap();
 
-        assert_eq!(size, (3, 3, 9));
+        assert_eq!(
+            field
+                .cells
+                .iter()
+                .filter(|cell| cell.is_anti_mined())
+                .count(),
+            4
+        );
+        assert_eq!(
+            field
+                .cells
+                .iter()
+                .map(|cell| cell.get_mines_amount() as u32)
+                .sum::<u32>(),
+            6
+        );
+        assert!(!field.get_cell(Position::new(0, 0)).unwrap().is_mined());
     }
 
     #[test]
-    fn get_cell_correctly_finds_the_cell_by_its_position() {
-        let field = Field::new(3, 3, 3).unwrap();
-        let cell = field.get_cell((0, 0));
+    fn anti_mines_decrease_the_numbers_and_stop_the_openings() {
+        // ⭕ -2  0
+        // ⭕ -1  1
+        // -1  0  💣
+        let mut field = Field::from_mine_and_anti_mine_positions(
+            3,
+            3,
+            &[Position::new(2, 2)],
+            &[Position::new(0, 0), Position::new(1, 0)],
+            Rules::default(),
+        )
+        .unwrap();
 
-        assert!(cell.is_some());
-        assert_eq!(cell.unwrap(), &field.grid[0][0])
+        let mines_around_amount =
+            |field: &Field, position| field.get_cell(position).unwrap().get_mines_around_amount();
+        assert_eq!(mines_around_amount(&field, Position::new(0, 1)), Some(-2));
+        assert_eq!(mines_around_amount(&field, Position::new(1, 1)), Some(-1));
+        assert_eq!(mines_around_amount(&field, Position::new(2, 1)), Some(0));
+
+        // The zero at the bottom is surrounded by an anti-mine and a mine, so it's not an opening.
+        assert_eq!(
+            field.open_cell(Position::new(2, 1)),
+            vec![Position::new(2, 1)]
+        );
+        assert_eq!(
+            field.open_cell(Position::new(0, 2)),
+            vec![
+                Position::new(0, 2),
+                Position::new(0, 1),
+                Position::new(1, 1),
+                Position::new(1, 2)
+            ]
+        );
     }
 
     #[test]
-    fn get_cell_returns_none_for_non_existing_cells() {
-        let field = Field::new(3, 3, 3).unwrap();
-        let cell = field.get_cell((10, 10));
+    fn anti_flags_count_as_minus_one_when_opening_the_surrounding_cells() {
+        // ⭕ 0 💣
+        // -1 0  1
+        let rules = Rules {
+            anti_mines_amount: 1,
+            ..Default::default()
+        };
+        let mut field = Field::from_mine_and_anti_mine_positions(
+            2,
+            3,
+            &[Position::new(0, 2)],
+            &[Position::new(0, 0)],
+            rules,
+        )
+        .unwrap();
+        field.open_cell(Position::new(0, 1));
 
-        assert!(cell.is_none());
+        // A single flag doesn't satisfy the zero.
+        field.toggle_cell_flag(Position::new(0, 2));
+        assert!(field.open_surrounding_cells(Position::new(0, 1)).is_empty());
+
+        // The second toggle turns the flag into an anti-flag.
+        field.toggle_cell_flag(Position::new(0, 0));
+        field.toggle_cell_flag(Position::new(0, 0));
+        assert_eq!(field.get_flagged_cells_amount(), 1);
+        assert_eq!(field.get_anti_flagged_cells_amount(), 1);
+
+        assert_eq!(field.open_surrounding_cells(Position::new(0, 1)).len(), 3);
+        assert!(field.check_all_non_mines_open());
+
+        // The third one removes it.
+        field.toggle_cell_flag(Position::new(0, 0));
+        assert_eq!(field.get_anti_flagged_cells_amount(), 0);
+        assert_eq!(field.cells_amounts, CellsAmounts::of(&field.cells));
+    }
+
+    #[test]
+    fn flag_surrounding_cells_flags_the_cells_that_have_to_be_mined() {
+        // 💣2 2 0
+        let rules = Rules {
+            max_mines_per_cell: 2,
+            ..Default::default()
+        };
+        let mut field = Field::from_mine_positions(1, 3, &[Position::new(0, 0); 2], rules).unwrap();
+        field.open_cell(Position::new(0, 2));
+
+        // The 0 has nothing to flag around it, while the 2 needs both mines in the only closed cell.
+        assert!(field.flag_surrounding_cells(Position::new(0, 2)).is_empty());
+        assert_eq!(
+            field.flag_surrounding_cells(Position::new(0, 1)),
+            vec![Position::new(0, 0)]
+        );
+        assert_eq!(
+            field
+                .get_cell(Position::new(0, 0))
+                .unwrap()
+                .get_flags_amount(),
+            2
+        );
+
+        // Once flagged, there's nothing left to do.
+        assert!(!field.check_surrounding_cells_flaggable(Position::new(0, 1)));
+        assert_eq!(field.cells_amounts, CellsAmounts::of(&field.cells));
     }
 
     #[test]
-    fn get_cell_mut_correctly_finds_the_cell_by_its_position() {
-        // let field = RefCell::new(Field::new(3, 3, 3).unwrap());
-        // let mut b = field.borrow_mut();
-        // let cell = b.get_cell_mut((0, 0));
-        //
-        // assert!(cell.is_some());
-        // assert_eq!(cell.unwrap(), &mut (field.borrow_mut().grid[0][0]));
+    fn flag_surrounding_cells_puts_the_anti_flags() {
+        // ⭕ -1 1 💣
+        let mut field = Field::from_mine_and_anti_mine_positions(
+            1,
+            4,
+            &[Position::new(0, 3)],
+            &[Position::new(0, 0)],
+            Rules::default(),
+        )
+        .unwrap();
+        field.open_cell(Position::new(0, 1));
+        field.open_cell(Position::new(0, 2));
+
+        // The 1 doesn't tell which of its neighbours is mined until the -1 is figured out.
+        assert!(field.flag_surrounding_cells(Position::new(0, 3)).is_empty());
+        field.flag_surrounding_cells(Position::new(0, 1));
+        field.flag_surrounding_cells(Position::new(0, 2));
+
+        assert!(field
+            .get_cell(Position::new(0, 0))
+            .unwrap()
+            .is_anti_flagged());
+        assert_eq!(
+            field
+                .get_cell(Position::new(0, 3))
+                .unwrap()
+                .get_flags_amount(),
+            1
+        );
     }
 
     #[test]
-    fn get_cell_mut_returns_none_for_non_existing_cells() {
-        let mut field = Field::new(3, 3, 3).unwrap();
-        let cell = field.get_cell_mut((10, 10));
+    fn question_marks_are_neither_flags_nor_obstacles() {
+        let rules = Rules {
+            question_marks: true,
+            ..Default::default()
+        };
+        let mut field = Field::from_mine_positions(2, 2, &[Position::new(0, 0)], rules).unwrap();
+
+        // The second toggle turns the flag into a question mark, and the third one removes it.
+        field.toggle_cell_flag(Position::new(1, 1));
+        assert_eq!(field.get_flagged_cells_amount(), 1);
+        field.toggle_cell_flag(Position::new(1, 1));
+        assert_eq!(field.get_flagged_cells_amount(), 0);
+        assert!(field
+            .get_cell(Position::new(1, 1))
+            .unwrap()
+            .is_question_marked());
 
-        assert!(cell.is_none());
+        assert_eq!(
+            field.open_cell(Position::new(1, 1)),
+            vec![Position::new(1, 1)]
+        );
+
+        // Without the rule, the flags are toggled the classic way.
+        let mut field =
+            Field::from_mine_positions(2, 2, &[Position::new(0, 0)], Rules::default()).unwrap();
+        field.toggle_cell_flag(Position::new(1, 1));
+        field.toggle_cell_flag(Position::new(1, 1));
+        assert!(!field
+            .get_cell(Position::new(1, 1))
+            .unwrap()
+            .is_question_marked());
     }
 
     #[test]
     fn get_mines_amount_returns_the_configured_amount_of_mines() {
         // Should be `3` in both cases, regardless of whether the field has been populated with mines or yet not.
-        let field = Field::new(3, 3, 3).unwrap();
+        let field = Field::new(3, 3, 3, Rules::default(), None).unwrap();
         assert_eq!(field.get_mines_amount(), 3);
 
         let field = create_stub_mined_field(false);
@@ -519,13 +2602,13 @@ mod test {
     fn open_cell_opens_the_requested_cell() {
         let mut field = create_stub_mined_field(false);
         field.update_mines_around_values();
-        field.open_cell((0, 2));
+        field.open_cell(Position::new(0, 2));
 
         // First, make sure the target cell is opened.
-        assert!(field.get_cell((0, 2)).unwrap().is_open());
+        assert!(field.get_cell(Position::new(0, 2)).unwrap().is_open());
 
         // Then get all the cells...
-        let mut all_cells: Vec<_> = field.grid.iter_mut().flatten().collect();
+        let mut all_cells: Vec<_> = field.cells.iter().collect();
 
         // ...And remove the target one. Make sure all the remaining cells are closed (no chain-opening in this case,
         // because the target cell has two mines around it).
@@ -533,28 +2616,71 @@ mod test {
         assert!(all_cells.iter().all(|cell| !cell.is_open()))
     }
 
+    #[test]
+    fn open_cell_returns_the_opened_cells() {
+        let mut field = create_stub_mined_field(true);
+        field.update_mines_around_values();
+        let opened_cells_positions = field.open_cell(Position::new(2, 0));
+
+        // The target cell goes first, and every open cell is listed exactly once.
+        assert_eq!(opened_cells_positions.first(), Some(&Position::new(2, 0)));
+        assert_eq!(
+            opened_cells_positions.len(),
+            field
+                .get_cells()
+                .iter()
+                .filter(|cell| cell.is_open())
+                .count()
+        );
+        assert!(opened_cells_positions
+            .iter()
+            .all(|position| field.get_cell(*position).unwrap().is_open()));
+
+        // Nothing gets opened the second time.
+        assert!(field.open_cell(Position::new(2, 0)).is_empty());
+    }
+
+    #[test]
+    fn open_cell_reveals_huge_openings() {
+        // The only mine is in the corner, so opening the opposite corner reveals the whole field at once. Opening the
+        // cells recursively would overflow the stack here.
+        let mut field =
+            Field::from_mine_positions(500, 500, &[Position::new(499, 499)], Rules::default())
+                .unwrap();
+        let opened_cells_positions = field.open_cell(Position::new(0, 0));
+
+        assert_eq!(opened_cells_positions.len(), 249_999);
+        assert!(field.check_all_non_mines_open());
+        assert!(!field.check_open_mines_exist());
+    }
+
     #[test]
     fn open_cell_chain_opens_empty_cells() {
         let mut field = create_stub_mined_field(true);
         field.update_mines_around_values();
-        field.open_cell((2, 0));
+        field.open_cell(Position::new(2, 0));
 
-        let open_cells_positions = [(2u8, 0u8), (3, 0), (3, 1), (3, 2)];
+        let open_cells_positions = [
+            Position::new(2, 0),
+            Position::new(3, 0),
+            Position::new(3, 1),
+            Position::new(3, 2),
+        ];
         let closed_cells_positions = [
-            (0u8, 0u8),
-            (0, 1),
-            (0, 2),
-            (1, 0),
-            (1, 1),
-            (1, 2),
-            (2, 2),
-            (2, 3),
+            Position::new(0, 0),
+            Position::new(0, 1),
+            Position::new(0, 2),
+            Position::new(1, 0),
+            Position::new(1, 1),
+            Position::new(1, 2),
+            Position::new(2, 2),
+            Position::new(2, 3),
         ];
 
         // A meta-assertion. Make sure we're not forgetting any cells.
         assert_eq!(
             field.get_size().2,
-            (open_cells_positions.len() + closed_cells_positions.len()) as u16
+            (open_cells_positions.len() + closed_cells_positions.len()) as u32
         );
 
         // Make sure all the cells which need to be open are open.
@@ -575,97 +2701,110 @@ mod test {
     fn open_surrounding_cells_opens_correct_cells() {
         let mut field = create_stub_mined_field(false);
         field.update_mines_around_values();
-        field.get_cell_mut((0, 0)).unwrap().toggle_flag();
-        field.get_cell_mut((0, 1)).unwrap().toggle_flag();
-        field.get_cell_mut((1, 2)).unwrap().toggle_flag();
-        field.open_cell((1, 1));
-        field.open_surrounding_cells((1, 1));
+        field.toggle_cell_flag(Position::new(0, 0));
+        field.toggle_cell_flag(Position::new(0, 1));
+        field.toggle_cell_flag(Position::new(1, 2));
+        field.open_cell(Position::new(1, 1));
+        field.open_surrounding_cells(Position::new(1, 1));
 
         // The above is the winning strategy. All the non-flagged cells should be opened by now.
         assert!(field
-            .grid
+            .cells
             .iter()
-            .flatten()
             .filter(|cell| !cell.is_flagged())
             .all(|cell| cell.is_open()));
     }
 
+    #[test]
+    fn open_surrounding_cells_counts_the_exploded_mines_as_flagged() {
+        let mut field = create_stub_mined_field(false);
+        field.update_mines_around_values();
+        field.toggle_cell_flag(Position::new(0, 0));
+        field.toggle_cell_flag(Position::new(0, 1));
+        field.open_cell(Position::new(1, 2)); // hit the third mine instead of flagging it
+        field.open_cell(Position::new(1, 1));
+        field.open_surrounding_cells(Position::new(1, 1));
+
+        assert!(field.check_all_non_mines_open());
+        assert_eq!(field.get_exploded_cells_amount(), 1);
+    }
+
     #[test]
     fn open_surrounding_cells_for_a_closed_cell_has_no_effect() {
         let mut field = create_stub_mined_field(false);
         field.update_mines_around_values();
-        field.get_cell_mut((0, 0)).unwrap().toggle_flag();
-        field.get_cell_mut((0, 1)).unwrap().toggle_flag();
-        field.get_cell_mut((1, 2)).unwrap().toggle_flag();
-        // field.open_cell((1, 1)); <- don't open the target cell
-        field.open_surrounding_cells((1, 1));
+        field.toggle_cell_flag(Position::new(0, 0));
+        field.toggle_cell_flag(Position::new(0, 1));
+        field.toggle_cell_flag(Position::new(1, 2));
+        // field.open_cell(Position::new(1, 1)); <- don't open the target cell
+        field.open_surrounding_cells(Position::new(1, 1));
 
         // All the cells must remain closed.
-        assert!(field.grid.iter().flatten().all(|cell| !cell.is_open()));
+        assert!(field.cells.iter().all(|cell| !cell.is_open()));
     }
 
     #[test]
     fn open_surrounding_cells_for_a_flagged_cell_has_no_effect() {
         let mut field = create_stub_mined_field(false);
         field.update_mines_around_values();
-        field.get_cell_mut((0, 0)).unwrap().toggle_flag();
-        field.get_cell_mut((0, 1)).unwrap().toggle_flag();
-        field.get_cell_mut((1, 2)).unwrap().toggle_flag();
-        field.get_cell_mut((1, 1)).unwrap().toggle_flag(); // flag the target cell
-        field.open_surrounding_cells((1, 1));
+        field.toggle_cell_flag(Position::new(0, 0));
+        field.toggle_cell_flag(Position::new(0, 1));
+        field.toggle_cell_flag(Position::new(1, 2));
+        field.toggle_cell_flag(Position::new(1, 1)); // flag the target cell
+        field.open_surrounding_cells(Position::new(1, 1));
 
         // All the cells must remain closed.
-        assert!(field.grid.iter().flatten().all(|cell| !cell.is_open()));
+        assert!(field.cells.iter().all(|cell| !cell.is_open()));
     }
 
     #[test]
     fn open_surrounding_cells_has_no_effect_on_incorrect_mines_around_amount() {
         let mut field = create_stub_mined_field(false);
         field.update_mines_around_values();
-        field.get_cell_mut((0, 0)).unwrap().toggle_flag();
-        field.get_cell_mut((0, 1)).unwrap().toggle_flag();
-        field.get_cell_mut((1, 2)).unwrap().toggle_flag();
-        field.open_cell((1, 1));
+        field.toggle_cell_flag(Position::new(0, 0));
+        field.toggle_cell_flag(Position::new(0, 1));
+        field.toggle_cell_flag(Position::new(1, 2));
+        field.open_cell(Position::new(1, 1));
 
         // So far so good, but add an excessive flag somewhere around
-        field.get_cell_mut((2, 0)).unwrap().toggle_flag();
+        field.toggle_cell_flag(Position::new(2, 0));
 
-        field.open_surrounding_cells((1, 1));
+        field.open_surrounding_cells(Position::new(1, 1));
 
         // All the cells (except for the target one) must remain closed.
-        let mut all_cells: Vec<_> = field.grid.iter().flatten().collect();
+        let mut all_cells: Vec<_> = field.cells.iter().collect();
         all_cells.remove(4);
         assert!(all_cells.into_iter().all(|cell| !cell.is_open()));
     }
 
     #[test]
     fn toggle_cell_flag_correctly_toggles_the_flag() {
-        let mut field = Field::new(3, 3, 3).unwrap();
-        assert!(!field.get_cell((1, 1)).unwrap().is_flagged());
+        let mut field = Field::new(3, 3, 3, Rules::default(), None).unwrap();
+        assert!(!field.get_cell(Position::new(1, 1)).unwrap().is_flagged());
 
-        field.toggle_cell_flag((1, 1));
-        assert!(field.get_cell((1, 1)).unwrap().is_flagged());
+        field.toggle_cell_flag(Position::new(1, 1));
+        assert!(field.get_cell(Position::new(1, 1)).unwrap().is_flagged());
 
-        field.toggle_cell_flag((1, 1));
-        assert!(!field.get_cell((1, 1)).unwrap().is_flagged());
+        field.toggle_cell_flag(Position::new(1, 1));
+        assert!(!field.get_cell(Position::new(1, 1)).unwrap().is_flagged());
     }
 
     #[test]
     fn toggle_cell_flag_has_no_effect_if_the_cell_is_not_found() {
-        let mut field = Field::new(3, 3, 3).unwrap();
+        let mut field = Field::new(3, 3, 3, Rules::default(), None).unwrap();
 
-        field.toggle_cell_flag((5, 5));
-        assert!(field.grid.iter().flatten().all(|cell| !cell.is_flagged()));
+        field.toggle_cell_flag(Position::new(5, 5));
+        assert!(field.cells.iter().all(|cell| !cell.is_flagged()));
     }
 
     #[test]
     fn get_flagged_cells_amount_returns_the_correct_amount_of_flagged_cells() {
-        let mut field = Field::new(3, 3, 3).unwrap();
+        let mut field = Field::new(3, 3, 3, Rules::default(), None).unwrap();
 
-        field.toggle_cell_flag((0, 0));
-        field.toggle_cell_flag((0, 1));
-        field.toggle_cell_flag((0, 2));
-        field.toggle_cell_flag((1, 0));
+        field.toggle_cell_flag(Position::new(0, 0));
+        field.toggle_cell_flag(Position::new(0, 1));
+        field.toggle_cell_flag(Position::new(0, 2));
+        field.toggle_cell_flag(Position::new(1, 0));
 
         assert_eq!(field.get_flagged_cells_amount(), 4);
     }
@@ -678,7 +2817,7 @@ mod test {
         assert!(!field.check_open_mines_exist());
 
         // Open a mined cell.
-        field.open_cell((0, 1));
+        field.open_cell(Position::new(0, 1));
 
         assert!(field.check_open_mines_exist());
     }
@@ -690,11 +2829,11 @@ mod test {
 
         assert!(!field.check_all_non_mines_open());
 
-        field.get_cell_mut((0, 0)).unwrap().toggle_flag();
-        field.get_cell_mut((0, 1)).unwrap().toggle_flag();
-        field.get_cell_mut((1, 2)).unwrap().toggle_flag();
-        field.open_cell((1, 1));
-        field.open_surrounding_cells((1, 1));
+        field.toggle_cell_flag(Position::new(0, 0));
+        field.toggle_cell_flag(Position::new(0, 1));
+        field.toggle_cell_flag(Position::new(1, 2));
+        field.open_cell(Position::new(1, 1));
+        field.open_surrounding_cells(Position::new(1, 1));
 
         // The above actions lead to winning the game. So by now the game is considered to be won, which is exactly what
         // the method checks.
@@ -708,18 +2847,17 @@ mod test {
 
         // There are 3 mines on the field. Flag only one of them, then call the `open_missed_mines` method and check
         // that only the other two are open.
-        field.toggle_cell_flag((0, 1));
+        field.toggle_cell_flag(Position::new(0, 1));
         field.open_missed_mines();
 
-        assert!(field.get_cell((0, 0)).unwrap().is_open());
-        assert!(field.get_cell((1, 2)).unwrap().is_open());
+        assert!(field.get_cell(Position::new(0, 0)).unwrap().is_open());
+        assert!(field.get_cell(Position::new(1, 2)).unwrap().is_open());
 
         // The total number of open cells by now should be two.
         assert_eq!(
             field
-                .grid
+                .cells
                 .iter()
-                .flatten()
                 .filter(|cell| cell.is_open())
                 .collect::<Vec<_>>()
                 .len(),
@@ -727,6 +2865,50 @@ mod test {
         );
     }
 
+    #[test]
+    fn the_cells_amounts_are_kept_up_to_date() {
+        let mut field = create_stub_mined_field(true);
+        field.update_mines_around_values();
+        let recount = |field: &Field| CellsAmounts::of(&field.cells);
+
+        field.open_cell(Position::new(3, 0));
+        field.toggle_cell_flag(Position::new(0, 0));
+        field.toggle_cell_flag(Position::new(1, 2));
+        field.toggle_cell_flag(Position::new(1, 2));
+        assert_eq!(field.cells_amounts, recount(&field));
+        assert_eq!(field.get_flagged_cells_amount(), 1);
+        assert!(!field.check_open_mines_exist());
+
+        // Take back the flag, just like undoing an action does.
+        field.replace_cell(Position::new(0, 0), {
+            let mut cell = Cell::new();
+            cell.mine();
+            cell
+        });
+        assert_eq!(field.cells_amounts, recount(&field));
+        assert_eq!(field.get_flagged_cells_amount(), 0);
+
+        field.open_cell(Position::new(0, 1));
+        field.open_missed_mines();
+        assert_eq!(field.cells_amounts, recount(&field));
+        assert!(field.check_open_mines_exist());
+    }
+
+    #[test]
+    fn the_cells_amounts_are_restored_after_deserialization() {
+        let mut field = create_stub_mined_field(false);
+        field.update_mines_around_values();
+        field.open_cell(Position::new(2, 0));
+        field.toggle_cell_flag(Position::new(0, 0));
+
+        let serialized = serde_json::to_string(&field).unwrap();
+        let deserialized = serde_json::from_str::<Field>(&serialized).unwrap();
+
+        assert_eq!(deserialized, field);
+        assert_eq!(deserialized.get_size(), (3, 3, 9));
+        assert_eq!(deserialized.get_flagged_cells_amount(), 1);
+    }
+
     // helpers
 
     // See the `/misc/stub_field_representation.png`.
@@ -738,34 +2920,37 @@ mod test {
         let mut grid = vec![
             vec![
                 {
-                    let mut cell = Cell::new((0, 0));
+                    let mut cell = Cell::new();
                     cell.mine();
                     cell
                 },
                 {
-                    let mut cell = Cell::new((0, 1));
+                    let mut cell = Cell::new();
                     cell.mine();
                     cell
                 },
-                Cell::new((0, 2)),
+                Cell::new(),
             ],
-            vec![Cell::new((1, 0)), Cell::new((1, 1)), {
-                let mut cell = Cell::new((1, 2));
+            vec![Cell::new(), Cell::new(), {
+                let mut cell = Cell::new();
                 cell.mine();
                 cell
             }],
-            vec![Cell::new((2, 0)), Cell::new((2, 1)), Cell::new((2, 2))],
+            vec![Cell::new(), Cell::new(), Cell::new()],
         ];
 
         if enlarged {
             // Add a row of empty cells.
-            let empty_row = vec![Cell::new((3, 0)), Cell::new((3, 1)), Cell::new((3, 2))];
+            let empty_row = vec![Cell::new(), Cell::new(), Cell::new()];
             grid.push(empty_row);
         }
 
-        Field {
+        Field::try_from(SerializedField {
             grid,
             mines_amount: 3,
-        }
+            rules: Rules::default(),
+            seed: 0,
+        })
+        .unwrap()
     }
 }
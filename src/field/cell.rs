@@ -1,38 +1,47 @@
+use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Display, Formatter};
 
 /// The cell variant.
 ///
-/// A cell can either be empty or contain a mine.
-#[derive(Debug, PartialEq, Eq)]
+/// A cell can either be empty, contain one or more mines or contain an anti-mine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 enum CellVariant {
     /// Represents an empty cell. The empty cell is one that doesn't contain a mine.
     ///
-    /// The parameter represents the number of mines around the cell.
-    Empty(u8),
+    /// The parameter represents the number of mines around the cell, minus the number of anti-mines around it.
+    Empty(i16),
     /// Represents a mined cell.
-    Mine,
+    ///
+    /// The parameter represents the number of mines in the cell, which is always at least one.
+    Mine(u8),
+    /// Represents a cell with an anti-mine, which is as deadly as a mine, but counts as `-1` towards the numbers.
+    AntiMine,
 }
 
 /// The cell's state.
 ///
-/// A cell can either be open or closed. When closed, it can also either be or not be flagged.
-#[derive(Debug, PartialEq, Eq)]
+/// A cell can either be open or closed. When closed, it can also have some flags, an anti-flag or a question mark put on
+/// it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 enum CellState {
     /// Represents a closed cell.
     ///
-    /// The boolean value indicates whether the cell is flagged (`true`) or not (`false`).
-    Closed(bool),
+    /// The parameter represents the number of flags put on the cell (`0` meaning the cell is not flagged).
+    Closed(u8),
+    /// Represents a closed cell marked as holding an anti-mine.
+    AntiFlagged,
+    /// Represents a closed cell marked with a question mark, i.e. as the one the player is unsure about.
+    QuestionMarked,
     /// Represents an open cell.
     Open,
 }
 
 /// The representation of a cell.
 ///
-/// A cell is described with its position in the field, a variant and a state.
-#[derive(PartialEq, Eq)]
+/// A cell is described with a variant and a state. It doesn't know its own position, which is only defined by the
+/// place the cell takes in the field.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Cell {
-    /// The cell's position in the field is represented with its row's and column's indices (respectively).
-    position: (u8, u8),
     /// The cell's variant is either of the [`CellVariant`] enum.
     variant: CellVariant,
     /// The cell's state is either of the [`CellState`] enum.
@@ -40,27 +49,55 @@ pub struct Cell {
 }
 
 impl Cell {
-    /// Creates a new closed not flagged empty [`Cell`] instance with the position provided.
-    pub fn new(position: (u8, u8)) -> Self {
+    /// Creates a new closed not flagged empty [`Cell`] instance.
+    pub fn new() -> Self {
         Cell {
-            position,
             variant: CellVariant::Empty(0),
-            state: CellState::Closed(false),
+            state: CellState::Closed(0),
         }
     }
 
-    /// Checks whether the cell is mined.
+    /// Checks whether the cell is mined, be it with mines or with an anti-mine (either way, opening it loses the game).
     pub fn is_mined(&self) -> bool {
-        self.variant == CellVariant::Mine
+        matches!(self.variant, CellVariant::Mine(_) | CellVariant::AntiMine)
+    }
+
+    /// Checks whether the cell holds an anti-mine.
+    pub fn is_anti_mined(&self) -> bool {
+        self.variant == CellVariant::AntiMine
+    }
+
+    /// Returns the number of mines in the cell (`0` if the cell is empty or holds an anti-mine).
+    pub fn get_mines_amount(&self) -> u8 {
+        if let CellVariant::Mine(mines_amount) = self.variant {
+            mines_amount
+        } else {
+            0
+        }
+    }
+
+    /// Returns what the cell adds to the numbers around it: the number of mines in it or `-1` for an anti-mine.
+    pub fn get_signed_mines_amount(&self) -> i16 {
+        if self.is_anti_mined() {
+            -1
+        } else {
+            self.get_mines_amount() as i16
+        }
     }
 
-    /// Mines the cell in-place.
+    /// Puts one more mine into the cell in-place.
     pub fn mine(&mut self) {
-        self.variant = CellVariant::Mine;
+        self.variant = CellVariant::Mine(self.get_mines_amount() + 1);
     }
 
-    /// Returns the amount of mines around the cell or [`None`] if the cell itself is mined.
-    pub fn get_mines_around_amount(&self) -> Option<u8> {
+    /// Puts an anti-mine into the cell in-place, replacing whatever the cell held.
+    pub fn anti_mine(&mut self) {
+        self.variant = CellVariant::AntiMine;
+    }
+
+    /// Returns the amount of mines around the cell (minus the amount of anti-mines around it) or [`None`] if the cell
+    /// itself is mined.
+    pub fn get_mines_around_amount(&self) -> Option<i16> {
         if let CellVariant::Empty(adjacent_mines_amount) = self.variant {
             Some(adjacent_mines_amount)
         } else {
@@ -68,12 +105,13 @@ impl Cell {
         }
     }
 
-    /// Increments the value representing the number of mines around the cell.
+    /// Increases the value representing the number of mines around the cell by the given amount, which is negative for
+    /// the anti-mines.
     ///
     /// Won't produce any effect if the cell itself is mined.
-    pub fn increment_mines_around_amount(&mut self) {
+    pub fn increase_mines_around_amount(&mut self, amount: i16) {
         if let CellVariant::Empty(adjacent_mines_amount) = self.variant {
-            self.variant = CellVariant::Empty(adjacent_mines_amount + 1);
+            self.variant = CellVariant::Empty(adjacent_mines_amount + amount);
         }
     }
 
@@ -87,12 +125,55 @@ impl Cell {
         self.state = CellState::Open;
     }
 
-    /// Checks whether the cell is flagged.
+    /// Checks whether the cell is an exploded one, i.e. is both mined and open.
+    pub fn is_exploded(&self) -> bool {
+        self.is_open() && self.is_mined()
+    }
+
+    /// Checks whether the cell is flagged, be it with flags or with an anti-flag.
     pub fn is_flagged(&self) -> bool {
-        if let CellState::Closed(is_flagged) = self.state {
-            is_flagged
+        self.get_flags_amount() > 0 || self.is_anti_flagged()
+    }
+
+    /// Checks whether the cell is anti-flagged.
+    pub fn is_anti_flagged(&self) -> bool {
+        self.state == CellState::AntiFlagged
+    }
+
+    /// Checks whether the cell is marked with a question mark. Such a cell is not considered flagged.
+    pub fn is_question_marked(&self) -> bool {
+        self.state == CellState::QuestionMarked
+    }
+
+    /// Returns the number of flags put on the cell (`0` if the cell is not flagged, is anti-flagged, is marked with a
+    /// question mark or is open).
+    pub fn get_flags_amount(&self) -> u8 {
+        if let CellState::Closed(flags_amount) = self.state {
+            flags_amount
+        } else {
+            0
+        }
+    }
+
+    /// Returns what the flags of the cell add up to when chording: the number of flags or `-1` for an anti-flag.
+    pub fn get_signed_flags_amount(&self) -> i16 {
+        if self.is_anti_flagged() {
+            -1
         } else {
-            false
+            self.get_flags_amount() as i16
+        }
+    }
+
+    /// Puts exactly the given number of flags on the cell in-place, `-1` meaning an anti-flag and `0` meaning no marks
+    /// at all.
+    ///
+    /// Won't produce any effect if the cell itself is open.
+    pub fn set_signed_flags_amount(&mut self, signed_flags_amount: i16) {
+        if !self.is_open() {
+            self.state = match signed_flags_amount {
+                ..=-1 => CellState::AntiFlagged,
+                flags_amount => CellState::Closed(flags_amount as u8),
+            };
         }
     }
 
@@ -100,56 +181,54 @@ impl Cell {
     ///
     /// Won't produce any effect if the cell itself is open.
     pub fn toggle_flag(&mut self) {
-        if let CellState::Closed(is_flagged) = self.state {
-            self.state = CellState::Closed(!is_flagged)
-        };
+        self.cycle_flags(1, false, false);
     }
 
-    /// Returns the positions of the cell's adjacent cells.
+    /// Puts one more flag on the cell in-place. Once there are `max_flags_amount` flags on it already, the flags get
+    /// replaced with an anti-flag (if `with_anti_flag` is `true`), then with a question mark (if `with_question_mark` is
+    /// `true`), and then removed.
     ///
-    /// The method implies an infinite field, so the caller must double check the returned values with respect
-    /// to the field's dimensions (so that there are no out-of-bounds cells' positions).
-    pub fn get_adjacent_cells_positions(&self) -> Vec<(u8, u8)> {
-        // Transform the cell's coordinates into `i16` to be able to subtract and add without overflow.
-        let (row_index, column_index) = (self.position.0 as i16, self.position.1 as i16);
-
-        // Create a 2D vector of all the cells' indices surrounding the current one.
-        vec![
-            vec![
-                (row_index - 1, column_index - 1),
-                (row_index, column_index - 1),
-                (row_index + 1, column_index - 1),
-            ],
-            vec![
-                (row_index - 1, column_index),
-                /*         current         */
-                (row_index + 1, column_index),
-            ],
-            vec![
-                (row_index - 1, column_index + 1),
-                (row_index, column_index + 1),
-                (row_index + 1, column_index + 1),
-            ],
-        ]
-        .into_iter()
-        // Flatten the 2D vector for an easier filtration.
-        .flatten()
-        .filter(|(row_index, column_index)| {
-            // Filter out all the cells' indices that go beyond the field's dimensions. Namely, where the row's and
-            // column's indices are less than 0 (the case of the first row/column).
-            *row_index >= 0 && *column_index >= 0
-        })
-        // Convert the coordinates back into `u8`.
-        .map(|(row_index, column_index)| (row_index as u8, column_index as u8))
-        .collect::<Vec<(u8, u8)>>()
+    /// Won't produce any effect if the cell itself is open.
+    pub fn cycle_flags(
+        &mut self,
+        max_flags_amount: u8,
+        with_anti_flag: bool,
+        with_question_mark: bool,
+    ) {
+        self.state = match self.state {
+            CellState::Closed(flags_amount) if flags_amount < max_flags_amount => {
+                CellState::Closed(flags_amount + 1)
+            }
+            CellState::Closed(_) if with_anti_flag => CellState::AntiFlagged,
+            CellState::Closed(_) | CellState::AntiFlagged if with_question_mark => {
+                CellState::QuestionMarked
+            }
+            CellState::Closed(_) | CellState::AntiFlagged | CellState::QuestionMarked => {
+                CellState::Closed(0)
+            }
+            CellState::Open => CellState::Open,
+        };
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell::new()
     }
 }
 
-/// The `Debug` implementation displays the closed cells as open.
+/// The `Debug` implementation displays the closed cells as open, unless they are marked. The cells with several mines (or
+/// flags) are followed by their number.
 impl Debug for Cell {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if let CellState::Closed(true) = self.state {
-            return write!(f, "🚩");
+        match self.state {
+            CellState::Closed(1) => return write!(f, "🚩"),
+            CellState::Closed(flags_amount) if flags_amount > 1 => {
+                return write!(f, "🚩{flags_amount}")
+            }
+            CellState::AntiFlagged => return write!(f, "🚫"),
+            CellState::QuestionMarked => return write!(f, "❓"),
+            _ => {}
         }
 
         match self.variant {
@@ -164,9 +243,13 @@ impl Debug for Cell {
                 7 => write!(f, "7️"),
                 8 => write!(f, "8️"),
                 9 => write!(f, "9️"),
-                _ => write!(f, "?"),
+                // some neighbourhoods have more than 9 cells, and the anti-mines make the numbers negative, but there
+                // are no keycap emojis for such numbers
+                n => write!(f, "{n}"),
             },
-            CellVariant::Mine => write!(f, "💣"),
+            CellVariant::Mine(1) => write!(f, "💣"),
+            CellVariant::Mine(mines_amount) => write!(f, "💣{mines_amount}"),
+            CellVariant::AntiMine => write!(f, "⭕"),
         }
     }
 }
@@ -176,13 +259,7 @@ impl Display for Cell {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self.state {
             // In the real game, the cells don't reveal their inner state.
-            CellState::Closed(is_flagged) => {
-                if is_flagged {
-                    write!(f, "🚩")
-                } else {
-                    write!(f, "⬛")
-                }
-            }
+            CellState::Closed(0) => write!(f, "⬛"),
             // The rest of the cases is successfully covered with the `Debug` trait's implementation.
             _ => write!(f, "{:?}", self),
         }
@@ -195,21 +272,20 @@ mod test {
 
     #[test]
     fn create_a_cell_instance() {
-        let cell = Cell::new((10, 10));
+        let cell = Cell::new();
 
         assert_eq!(
             cell,
             Cell {
-                position: (10, 10),
                 variant: CellVariant::Empty(0),
-                state: CellState::Closed(false)
+                state: CellState::Closed(0)
             }
         );
     }
 
     #[test]
     fn mine_cell_and_is_mine() {
-        let mut cell = Cell::new((10, 10));
+        let mut cell = Cell::new();
         assert!(!cell.is_mined());
 
         cell.mine();
@@ -217,42 +293,69 @@ mod test {
     }
 
     #[test]
-    fn increment_mines_around_amount_and_get_mines_around_amount_for_an_empty_cell() {
-        let mut cell = Cell::new((10, 10));
+    fn mine_cell_several_times_and_get_mines_amount() {
+        let mut cell = Cell::new();
+        assert_eq!(cell.get_mines_amount(), 0);
+
+        cell.mine();
+        cell.mine();
+        cell.mine();
+
+        assert!(cell.is_mined());
+        assert_eq!(cell.get_mines_amount(), 3);
+    }
+
+    #[test]
+    fn increase_mines_around_amount_and_get_mines_around_amount_for_an_empty_cell() {
+        let mut cell = Cell::new();
         assert_eq!(cell.get_mines_around_amount().unwrap(), 0);
 
-        cell.increment_mines_around_amount();
-        cell.increment_mines_around_amount();
-        cell.increment_mines_around_amount();
+        cell.increase_mines_around_amount(1);
+        cell.increase_mines_around_amount(1);
+        cell.increase_mines_around_amount(2);
 
-        assert_eq!(cell.get_mines_around_amount().unwrap(), 3);
+        assert_eq!(cell.get_mines_around_amount().unwrap(), 4);
     }
 
     #[test]
-    fn increment_mines_around_amount_and_get_mines_around_amount_for_a_mined_cell() {
-        let mut cell = Cell::new((10, 10));
+    fn increase_mines_around_amount_and_get_mines_around_amount_for_a_mined_cell() {
+        let mut cell = Cell::new();
         cell.mine();
         assert_eq!(cell.get_mines_around_amount(), None);
 
-        cell.increment_mines_around_amount();
-        cell.increment_mines_around_amount();
-        cell.increment_mines_around_amount();
+        cell.increase_mines_around_amount(1);
+        cell.increase_mines_around_amount(1);
+        cell.increase_mines_around_amount(2);
 
         assert_eq!(cell.get_mines_around_amount(), None);
     }
 
     #[test]
     fn open_and_is_open() {
-        let mut cell = Cell::new((10, 10));
+        let mut cell = Cell::new();
         assert!(!cell.is_open());
 
         cell.open();
         assert!(cell.is_open());
     }
 
+    #[test]
+    fn open_and_is_exploded() {
+        let mut cell = Cell::new();
+        cell.open();
+        assert!(!cell.is_exploded());
+
+        let mut cell = Cell::new();
+        cell.mine();
+        assert!(!cell.is_exploded());
+
+        cell.open();
+        assert!(cell.is_exploded());
+    }
+
     #[test]
     fn toggle_flag_and_is_flagged_for_an_empty_cell() {
-        let mut cell = Cell::new((10, 10));
+        let mut cell = Cell::new();
         assert!(!cell.is_flagged());
 
         cell.toggle_flag();
@@ -264,7 +367,7 @@ mod test {
 
     #[test]
     fn toggle_flag_and_is_flagged_for_an_open_cell() {
-        let mut cell = Cell::new((10, 10));
+        let mut cell = Cell::new();
         cell.open();
         assert!(!cell.is_flagged());
 
@@ -276,52 +379,95 @@ mod test {
     }
 
     #[test]
-    fn get_adjacent_cells_positions_for_middle_cell() {
-        let cell = Cell::new((10, 10));
-        let adjacent_cells_positions = cell.get_adjacent_cells_positions();
+    fn cycle_flags_and_get_flags_amount() {
+        let mut cell = Cell::new();
+        assert_eq!(cell.get_flags_amount(), 0);
 
-        assert_eq!(
-            adjacent_cells_positions,
-            [
-                (9, 9),
-                (10, 9),
-                (11, 9),
-                (9, 10),
-                (11, 10),
-                (9, 11),
-                (10, 11),
-                (11, 11)
-            ]
-        );
+        cell.cycle_flags(3, false, false);
+        cell.cycle_flags(3, false, false);
+        assert_eq!(cell.get_flags_amount(), 2);
+        assert!(cell.is_flagged());
+
+        cell.cycle_flags(3, false, false);
+        assert_eq!(cell.get_flags_amount(), 3);
+
+        // the flags are removed after the last one
+        cell.cycle_flags(3, false, false);
+        assert_eq!(cell.get_flags_amount(), 0);
+        assert!(!cell.is_flagged());
     }
 
     #[test]
-    fn get_adjacent_cells_positions_for_0th_row() {
-        let cell = Cell::new((0, 10));
-        let adjacent_cells_positions = cell.get_adjacent_cells_positions();
+    fn cycle_flags_goes_through_the_anti_flag() {
+        let mut cell = Cell::new();
 
-        assert_eq!(
-            adjacent_cells_positions,
-            [(0, 9), (1, 9), (1, 10), (0, 11), (1, 11)]
-        );
+        cell.cycle_flags(1, true, false);
+        assert_eq!(cell.get_signed_flags_amount(), 1);
+
+        cell.cycle_flags(1, true, false);
+        assert!(cell.is_anti_flagged() && cell.is_flagged());
+        assert_eq!(cell.get_flags_amount(), 0);
+        assert_eq!(cell.get_signed_flags_amount(), -1);
+
+        cell.cycle_flags(1, true, false);
+        assert!(!cell.is_flagged());
     }
 
     #[test]
-    fn get_adjacent_cells_positions_for_0th_column() {
-        let cell = Cell::new((10, 0));
-        let adjacent_cells_positions = cell.get_adjacent_cells_positions();
+    fn cycle_flags_goes_through_the_question_mark() {
+        let mut cell = Cell::new();
 
-        assert_eq!(
-            adjacent_cells_positions,
-            [(9, 0), (11, 0), (9, 1), (10, 1), (11, 1)]
-        );
+        cell.cycle_flags(1, true, true);
+        cell.cycle_flags(1, true, true);
+        assert!(cell.is_anti_flagged());
+
+        cell.cycle_flags(1, true, true);
+        assert!(cell.is_question_marked() && !cell.is_flagged());
+        assert_eq!(cell.get_signed_flags_amount(), 0);
+        assert_eq!(format!("{cell}"), "❓");
+
+        cell.cycle_flags(1, true, true);
+        assert!(!cell.is_question_marked());
+        assert_eq!(format!("{cell}"), "⬛");
+
+        // the question mark goes right after the flags if there are no anti-flags
+        cell.cycle_flags(1, false, true);
+        cell.cycle_flags(1, false, true);
+        assert!(cell.is_question_marked());
+
+        cell.open();
+        assert!(cell.is_open() && !cell.is_question_marked());
     }
 
     #[test]
-    fn get_adjacent_cells_positions_for_0_0_edge_case() {
-        let cell = Cell::new((0, 0));
-        let adjacent_cells_positions = cell.get_adjacent_cells_positions();
+    fn anti_mine_cell_and_get_signed_mines_amount() {
+        let mut cell = Cell::new();
+        cell.anti_mine();
+
+        assert!(cell.is_mined() && cell.is_anti_mined());
+        assert_eq!(cell.get_mines_amount(), 0);
+        assert_eq!(cell.get_signed_mines_amount(), -1);
+        assert_eq!(cell.get_mines_around_amount(), None);
+    }
+
+    #[test]
+    fn debug_displays_the_numbers_above_9() {
+        let mut cell = Cell::new();
+        cell.variant = CellVariant::Empty(24);
+
+        assert_eq!(format!("{cell:?}"), "24");
+    }
+
+    #[test]
+    fn debug_displays_the_numbers_of_mines_and_flags() {
+        let mut cell = Cell::new();
+        cell.mine();
+        cell.mine();
+        assert_eq!(format!("{cell:?}"), "💣2");
 
-        assert_eq!(adjacent_cells_positions, [(1, 0), (0, 1), (1, 1)]);
+        cell.cycle_flags(3, false, false);
+        cell.cycle_flags(3, false, false);
+        cell.cycle_flags(3, false, false);
+        assert_eq!(format!("{cell:?}"), "🚩3");
     }
 }
@@ -0,0 +1,205 @@
+//! The difficulty metrics of a field.
+//!
+//! The metrics are the standard ones used to compare the runs on different fields. Most importantly, the 3BV (Bechtel's
+//! Board Benchmark Value) is the minimum number of left clicks needed to solve the field without flagging or chording:
+//! one per each opening plus one per each numbered cell which doesn't border any opening.
+
+use super::{position::Position, Field};
+use std::collections::{BTreeSet, VecDeque};
+
+/// The difficulty metrics of a field (see [`Field::get_metrics`]).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FieldMetrics {
+    /// The minimum number of left clicks needed to solve the field.
+    pub three_bv: u32,
+    /// The part of the 3BV that has already been solved: the number of the fully open openings plus the number of the
+    /// open numbered cells which don't border any opening.
+    pub solved_three_bv: u32,
+    /// The number of openings, i.e. the connected regions of the cells with no mines around them.
+    pub openings_amount: u32,
+    /// The number of the numbered cells which don't border any opening, so that each of them has to be opened
+    /// separately.
+    pub isolated_cells_amount: u32,
+    /// The number of islands, i.e. the connected groups of the isolated numbered cells.
+    pub islands_amount: u32,
+}
+
+impl Field {
+    /// Calculates the difficulty metrics of the field.
+    ///
+    /// The metrics only make sense after the field has been populated with mines.
+    pub fn get_metrics(&self) -> FieldMetrics {
+        let mut metrics = FieldMetrics::default();
+
+        // The cells that have been assigned to an opening or to an island.
+        let mut visited_cells = BTreeSet::new();
+
+        for position in self.get_positions() {
+            if visited_cells.contains(&position) || !self.check_opening(position) {
+                continue;
+            }
+
+            // Every opening gets explored starting from one of its cells.
+            let opening =
+                self.collect_connected_cells(position, |position| self.check_opening(position));
+
+            metrics.openings_amount += 1;
+            if opening
+                .iter()
+                .all(|position| self.get_cell(*position).unwrap().is_open())
+            {
+                metrics.solved_three_bv += 1;
+            }
+
+            visited_cells.extend(opening);
+        }
+
+        // With anti-mines in the field, a numbered cell might as well show a 0 (or a negative number).
+        let is_isolated = |position: Position| {
+            self.get_cell(position)
+                .is_some_and(|cell| cell.get_mines_around_amount().is_some())
+                && !self.check_opening(position)
+                && !self
+                    .get_adjacent_positions(position)
+                    .into_iter()
+                    .any(|adjacent_position| self.check_opening(adjacent_position))
+        };
+
+        for position in self.get_positions() {
+            if visited_cells.contains(&position) || !is_isolated(position) {
+                continue;
+            }
+
+            let island = self.collect_connected_cells(position, is_isolated);
+
+            metrics.islands_amount += 1;
+            metrics.isolated_cells_amount += island.len() as u32;
+            metrics.solved_three_bv += island
+                .iter()
+                .filter(|position| self.get_cell(**position).unwrap().is_open())
+                .count() as u32;
+
+            visited_cells.extend(island);
+        }
+
+        metrics.three_bv = metrics.openings_amount + metrics.isolated_cells_amount;
+
+        metrics
+    }
+
+    /// Collects the cells connected to the starting one through the adjacent cells matching the predicate.
+    fn collect_connected_cells(
+        &self,
+        starting_position: Position,
+        predicate: impl Fn(Position) -> bool,
+    ) -> Vec<Position> {
+        let mut connected_cells = vec![starting_position];
+        let mut visited_cells = BTreeSet::from([starting_position]);
+        let mut queue = VecDeque::from([starting_position]);
+
+        while let Some(position) = queue.pop_front() {
+            for adjacent_position in self.get_adjacent_positions(position) {
+                if predicate(adjacent_position) && visited_cells.insert(adjacent_position) {
+                    connected_cells.push(adjacent_position);
+                    queue.push_back(adjacent_position);
+                }
+            }
+        }
+
+        connected_cells
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FieldMetrics;
+    use crate::{
+        field::{position::Position, Field},
+        rules::Rules,
+    };
+
+    #[test]
+    fn get_metrics_counts_openings_and_isolated_cells() {
+        // 0 1 * 2 *
+        // 0 1 1 2 1
+        // 0 0 0 0 0
+        // 1 1 0 1 1
+        // * 1 0 1 *
+        let field = Field::from_mine_positions(
+            5,
+            5,
+            &[
+                Position::new(0, 2),
+                Position::new(0, 4),
+                Position::new(4, 0),
+                Position::new(4, 4),
+            ],
+            Rules::default(),
+        )
+        .unwrap();
+
+        // The only opening borders all the numbers except for the 2 at the top, which is isolated.
+        assert_eq!(
+            field.get_metrics(),
+            FieldMetrics {
+                three_bv: 2,
+                solved_three_bv: 0,
+                openings_amount: 1,
+                isolated_cells_amount: 1,
+                islands_amount: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn get_metrics_groups_isolated_cells_into_islands() {
+        // * 1 0 1 *
+        // 2 2 2 2 2
+        // 1 * 2 * 1
+        // 2 2 4 2 2
+        // 1 * 2 * 1
+        let field = Field::from_mine_positions(
+            5,
+            5,
+            &[
+                Position::new(0, 0),
+                Position::new(0, 4),
+                Position::new(2, 1),
+                Position::new(2, 3),
+                Position::new(4, 1),
+                Position::new(4, 3),
+            ],
+            Rules::default(),
+        )
+        .unwrap();
+
+        // All the numbers except for the five around the opening are isolated, and all of them are connected.
+        let metrics = field.get_metrics();
+        assert_eq!(metrics.openings_amount, 1);
+        assert_eq!(metrics.isolated_cells_amount, 13);
+        assert_eq!(metrics.islands_amount, 1);
+        assert_eq!(metrics.three_bv, 14);
+    }
+
+    #[test]
+    fn get_metrics_counts_the_solved_part() {
+        let mut field = Field::from_mine_positions(
+            5,
+            5,
+            &[
+                Position::new(0, 2),
+                Position::new(0, 4),
+                Position::new(4, 0),
+                Position::new(4, 4),
+            ],
+            Rules::default(),
+        )
+        .unwrap();
+
+        field.open_cell(Position::new(0, 3));
+        assert_eq!(field.get_metrics().solved_three_bv, 1);
+
+        field.open_cell(Position::new(2, 2));
+        assert_eq!(field.get_metrics().solved_three_bv, 2);
+    }
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// The position of a cell in the field.
+///
+/// The indices are wide enough for the fields of up to 65535 rows and as many columns. The positions are ordered row by
+/// row, and are (de)serialized as the `[row_index, column_index]` pairs.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(from = "(u16, u16)", into = "(u16, u16)")]
+pub struct Position {
+    /// The index of the cell's row.
+    pub row_index: u16,
+    /// The index of the cell's column.
+    pub column_index: u16,
+}
+
+impl Position {
+    /// Creates a new [`Position`] with the provided row's and column's indices (respectively).
+    pub const fn new(row_index: u16, column_index: u16) -> Self {
+        Position {
+            row_index,
+            column_index,
+        }
+    }
+}
+
+impl From<(u16, u16)> for Position {
+    fn from((row_index, column_index): (u16, u16)) -> Self {
+        Position::new(row_index, column_index)
+    }
+}
+
+impl From<Position> for (u16, u16) {
+    fn from(position: Position) -> Self {
+        (position.row_index, position.column_index)
+    }
+}
+
+/// The `Display` implementation represents the position as the `(row_index, column_index)` pair.
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.row_index, self.column_index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Position;
+
+    #[test]
+    fn positions_are_ordered_row_by_row() {
+        assert!(Position::new(0, 5) < Position::new(1, 0));
+        assert!(Position::new(1, 0) < Position::new(1, 1));
+    }
+
+    #[test]
+    fn a_position_is_serialized_as_a_pair() {
+        let position = Position::new(300, 2);
+        let serialized = serde_json::to_string(&position).unwrap();
+
+        assert_eq!(serialized, "[300,2]");
+        assert_eq!(
+            serde_json::from_str::<Position>(&serialized).unwrap(),
+            position
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! The record of the actions taken during a game.
+//!
+//! Each action is stored along with the cells it has changed and the statuses of the game before and after it, so
+//! that it can be taken back and re-applied without performing it once again.
+
+use crate::field::{cell::Cell, position::Position, Field};
+use crate::{MinesweeperAction, MinesweeperStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// A single cell changed by an action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellChange {
+    /// The position of the cell in the field.
+    pub position: Position,
+    /// The cell as it was before the action.
+    pub before: Cell,
+    /// The cell as it became after the action.
+    pub after: Cell,
+}
+
+/// An action applied to the game along with everything it has changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// The action itself, exactly as it's been requested.
+    pub action: MinesweeperAction,
+    /// The cells changed by the action.
+    pub changes: Vec<CellChange>,
+    /// The game status before the action.
+    pub status_before: MinesweeperStatus,
+    /// The game status after the action.
+    pub status_after: MinesweeperStatus,
+}
+
+impl HistoryEntry {
+    /// Creates an entry for the action from the cells it has changed (see [`Field::take_recorded_changes`]) and the
+    /// field after it. Only the earliest state of a cell changed several times is taken as the one before the action.
+    pub(crate) fn new(
+        action: MinesweeperAction,
+        changed_cells: Vec<(Position, Cell)>,
+        field_after: &Field,
+        status_before: MinesweeperStatus,
+        status_after: MinesweeperStatus,
+    ) -> Self {
+        let mut changed_positions = HashSet::new();
+
+        let changes = changed_cells
+            .into_iter()
+            .filter(|(position, _)| changed_positions.insert(*position))
+            .filter_map(|(position, before)| {
+                let after = field_after.get_cell(position)?.clone();
+
+                (before != after).then_some(CellChange {
+                    position,
+                    before,
+                    after,
+                })
+            })
+            .collect();
+
+        HistoryEntry {
+            action,
+            changes,
+            status_before,
+            status_after,
+        }
+    }
+}
+
+/// The history of a game: the actions that have been applied and the ones that have been undone.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    /// The applied actions, the most recent one going last.
+    applied: Vec<HistoryEntry>,
+    /// The undone actions, the most recently undone one going last.
+    undone: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Returns the applied actions in the order they've been taken.
+    pub fn get_applied_entries(&self) -> &[HistoryEntry] {
+        &self.applied
+    }
+
+    /// Returns the undone actions, the most recently undone one going last.
+    pub fn get_undone_entries(&self) -> &[HistoryEntry] {
+        &self.undone
+    }
+
+    /// Records a newly applied action. The undone actions can't be redone after that.
+    pub(crate) fn record(&mut self, entry: HistoryEntry) {
+        self.applied.push(entry);
+        self.undone.clear();
+    }
+
+    /// Moves the most recently applied action to the undone ones and returns it (if there's any).
+    pub(crate) fn undo(&mut self) -> Option<&HistoryEntry> {
+        let entry = self.applied.pop()?;
+        self.undone.push(entry);
+
+        self.undone.last()
+    }
+
+    /// Moves the most recently undone action back to the applied ones and returns it (if there's any).
+    pub(crate) fn redo(&mut self) -> Option<&HistoryEntry> {
+        let entry = self.undone.pop()?;
+        self.applied.push(entry);
+
+        self.applied.last()
+    }
+}
@@ -1,7 +1,18 @@
+pub mod difficulty;
 pub mod field;
+pub mod history;
+pub mod rules;
+pub mod solver;
+pub mod stats;
 mod stopwatch;
 
-use field::{Field, FieldError};
+use difficulty::Difficulty;
+use field::{position::Position, Field, FieldError};
+use history::{History, HistoryEntry};
+use rules::{Rules, UndoPolicy};
+use serde::{Deserialize, Serialize};
+use solver::probability::calculate_mine_probabilities;
+use stats::{ClicksAmounts, GameStats};
 use stopwatch::Stopwatch;
 
 /// The enum represents the variants of everything that can possibly go wrong during the game.
@@ -13,6 +24,12 @@ pub enum MinesweeperError {
     FieldError(FieldError),
     /// The error indicates that the game has already ended, and therefore the requested action could not be performed.
     GameAlreadyEnded,
+    /// The error indicates that there are no actions to undo.
+    NothingToUndo,
+    /// The error indicates that there are no undone actions to redo.
+    NothingToRedo,
+    /// The error indicates that the game's [`UndoPolicy`] doesn't allow to undo the action at the moment.
+    UndoForbidden,
 }
 
 impl From<FieldError> for MinesweeperError {
@@ -22,11 +39,12 @@ impl From<FieldError> for MinesweeperError {
 }
 
 /// The status of a game.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum MinesweeperStatus {
     /// After the field has been created, but before it has been initialized with mines and numbers.
     Pre,
-    /// An ongoing game.
+    /// An ongoing game. With several lives (see [`Rules::lives_amount`]), the game stays on after a mine is hit, as long
+    /// as there are lives left (see [`Minesweeper::get_lives_left`]).
     On,
     /// A paused game.
     Pause,
@@ -35,23 +53,44 @@ pub enum MinesweeperStatus {
 }
 
 /// Describes all the possible action a user can take.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MinesweeperAction {
     /// A request to open a cell by its position.
-    OpenCell((u8, u8)),
+    OpenCell(Position),
     /// A request to open the cells adjacent to the one with the provided position.
-    OpenSurroundingCells((u8, u8)),
-    /// This action is a combination of the two ones above with it's automatically deciding which one to use exactly.
+    OpenSurroundingCells(Position),
+    /// A request to flag the cells adjacent to the one with the provided position, given that the number of the cell
+    /// requires all of them to be mined (see [`Field::flag_surrounding_cells`]).
+    FlagSurroundingCells(Position),
+    /// This action is a combination of the three ones above with it's automatically deciding which one to use exactly.
+    /// If the rules allow for it (see [`Rules::auto_flag`]), the surrounding cells of an open cell get flagged
+    /// whenever possible, and opened otherwise.
     ///
     /// This is intended to be used with frontends which have a limited number of inputs, so that both actions could use
     /// the same trigger.
-    OpenCellOrSurroundingCells((u8, u8)),
+    OpenCellOrSurroundingCells(Position),
     /// A request to flag a cell by its position.
-    FlagCell((u8, u8)),
+    FlagCell(Position),
+    /// A request to suggest the cell to open next (see [`Minesweeper::get_hint`]). Using hints makes the game assisted,
+    /// except for the hints taken before the field gets populated with mines, which reveal nothing about them.
+    Hint,
+}
+
+/// A suggestion of the cell to open next.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Hint {
+    /// The position of the suggested cell.
+    pub position: Position,
+    /// The probability of the cell to be mined: `0.0` for the provably safe cells, or the lowest probability among all
+    /// the closed cells if none of them is provably safe.
+    pub probability: f64,
 }
 
 /// The struct representing a Minesweeper game itself.
-#[derive(Debug)]
+///
+/// The game can be (de)serialized with `serde` to be saved and resumed later. The stopwatch is restored in the same
+/// state it's been saved in: if the game has been ongoing, the time keeps running right after the deserialization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Minesweeper {
     /// The field used in the game.
     field: Field,
@@ -59,35 +98,109 @@ pub struct Minesweeper {
     status: MinesweeperStatus,
     /// The in-game stopwatch. It's started as soon as the first cell gets opened and is paused when the game is paused.
     stopwatch: Stopwatch,
+    /// The actions taken during the game.
+    #[serde(default)]
+    history: History,
+    /// The number of times an action has been undone.
+    #[serde(default)]
+    undos_amount: u32,
+    /// The number of hints used.
+    #[serde(default)]
+    hints_amount: u32,
+    /// The most recent hint. It's only kept until the next action is taken.
+    #[serde(default)]
+    hint: Option<Hint>,
+    /// The numbers of the actions taken during the game.
+    #[serde(default)]
+    clicks: ClicksAmounts,
 }
 
 impl Minesweeper {
+    /// Creates a new game with a field of the provided dimensions and number of mines, played by the provided rules.
+    ///
+    /// The optional seed makes the mines layout reproducible: the same seed along with the same first opened cell
+    /// always results in the same field. A random seed is used when none is provided.
     pub fn new(
-        rows_amount: u8,
-        columns_amount: u8,
-        mines_amount: u16,
+        rows_amount: u16,
+        columns_amount: u16,
+        mines_amount: u32,
+        rules: Rules,
+        seed: Option<u64>,
     ) -> Result<Self, MinesweeperError> {
-        let field = Field::new(rows_amount, columns_amount, mines_amount)?;
+        let field = Field::new(rows_amount, columns_amount, mines_amount, rules, seed)?;
 
         Ok(Minesweeper {
             field,
             status: MinesweeperStatus::Pre,
             stopwatch: Stopwatch::default(),
+            history: History::default(),
+            undos_amount: 0,
+            hints_amount: 0,
+            hint: None,
+            clicks: ClicksAmounts::default(),
         })
     }
 
+    /// Creates a new game of the given difficulty, e.g. `Minesweeper::with_difficulty(Difficulty::Expert, rules, None)`.
+    pub fn with_difficulty(
+        difficulty: Difficulty,
+        rules: Rules,
+        seed: Option<u64>,
+    ) -> Result<Self, MinesweeperError> {
+        let (rows_amount, columns_amount, mines_amount) = difficulty.get_size();
+
+        Minesweeper::new(rows_amount, columns_amount, mines_amount, rules, seed)
+    }
+
+    /// Creates a new game with an already mined field, e.g. one created with [`Field::from_mine_positions`].
+    ///
+    /// The field is not going to be re-populated with mines when the first cell gets opened, so the first click safety
+    /// is not guaranteed in this case.
+    pub fn from_field(field: Field) -> Self {
+        Minesweeper {
+            field,
+            status: MinesweeperStatus::Pre,
+            stopwatch: Stopwatch::default(),
+            history: History::default(),
+            undos_amount: 0,
+            hints_amount: 0,
+            hint: None,
+            clicks: ClicksAmounts::default(),
+        }
+    }
+
     /// Returns the game's field borrowing.
     pub fn get_field(&self) -> &Field {
         &self.field
     }
 
+    /// Returns the rules the game is played by.
+    pub fn get_rules(&self) -> Rules {
+        self.field.get_rules()
+    }
+
+    /// Returns the seed the game's field is (or is going to be) populated with mines with.
+    ///
+    /// Creating a new game with the same seed and opening the same cell first reproduces the very same field.
+    pub fn get_seed(&self) -> u64 {
+        self.field.get_seed()
+    }
+
     /// Returns the game's status borrowing.
     pub fn get_status(&self) -> &MinesweeperStatus {
         &self.status
     }
 
+    /// Returns the history of the actions taken during the game.
+    pub fn get_history(&self) -> &History {
+        &self.history
+    }
+
     /// The method performs the requested action, updates the status of the game and returns it.
     ///
+    /// The action is recorded in the game's history, unless it hasn't changed anything or the game's [`UndoPolicy`]
+    /// doesn't allow to undo the actions at all.
+    ///
     /// Might fail with a [`MinesweeperError`] in case something goes wrong.
     pub fn take_action(
         &mut self,
@@ -104,12 +217,59 @@ impl Minesweeper {
             return Ok(&MinesweeperStatus::Pause);
         }
 
+        let status_before = self.status.clone();
+        let clicks_before = self.clicks;
+
+        // The hint is only relevant until the field changes.
+        if action_type != MinesweeperAction::Hint {
+            self.hint = None;
+        }
+
+        // Only the cells the action changes are recorded, so that the history doesn't cost a pass over the whole field.
+        self.field.start_recording_changes();
+
+        if let Err(error) = self.perform_action(action_type) {
+            self.field.take_recorded_changes();
+            return Err(error);
+        }
+
+        self.update_status();
+
+        let changed_cells = self.field.take_recorded_changes();
+
+        if changed_cells.is_empty() && status_before == self.status {
+            // Only the actions counted as clicks might be wasted (e.g., neither the hints nor the actions targeting a
+            // cell beyond the field's bounds are).
+            if self.clicks != clicks_before {
+                self.clicks.wasted_clicks_amount += 1;
+            }
+        } else if self.get_rules().undo != UndoPolicy::Forbidden {
+            // There's no use in keeping the history of a game whose actions can't be undone.
+            self.history.record(HistoryEntry::new(
+                action_type,
+                changed_cells,
+                &self.field,
+                status_before,
+                self.status.clone(),
+            ));
+        }
+
+        Ok(&self.status)
+    }
+
+    /// A private helper that performs the requested action without updating the status of the game.
+    fn perform_action(&mut self, action_type: MinesweeperAction) -> Result<(), MinesweeperError> {
         // Match and perform the requested action.
         match action_type {
             MinesweeperAction::OpenCell(cell_position) => {
+                self.clicks.left_clicks_amount += 1;
+
                 if let MinesweeperStatus::On = self.status {
                 } else {
-                    self.field.populate_with_mines(Some(cell_position))?;
+                    // The field might have been mined beforehand (see `Minesweeper::from_field`).
+                    if !self.field.check_mines_exist() {
+                        self.field.populate_with_mines(Some(cell_position))?;
+                    }
 
                     self.status = MinesweeperStatus::On;
 
@@ -119,31 +279,191 @@ impl Minesweeper {
                 self.field.open_cell(cell_position);
             }
             MinesweeperAction::OpenSurroundingCells(cell_position) => {
+                self.clicks.chords_amount += 1;
                 self.field.open_surrounding_cells(cell_position);
             }
+            MinesweeperAction::FlagSurroundingCells(cell_position) => {
+                self.clicks.chords_amount += 1;
+                self.field.flag_surrounding_cells(cell_position);
+            }
             MinesweeperAction::OpenCellOrSurroundingCells(cell_position) => {
                 let target_cell = self.field.get_cell(cell_position);
 
                 if let Some(cell) = target_cell {
                     // We're not calling the underlying method here directly because this action is just an alias.
-                    if cell.is_open() {
-                        // For the already-open cells, perform the `OpenSurroundingCells` action.
-                        self.take_action(MinesweeperAction::OpenSurroundingCells(cell_position))?;
+                    if cell.is_open()
+                        && self.get_rules().auto_flag
+                        && self.field.check_surrounding_cells_flaggable(cell_position)
+                    {
+                        // For the already-open cells whose surrounding cells can only be mined, perform the
+                        // `FlagSurroundingCells` action.
+                        self.perform_action(MinesweeperAction::FlagSurroundingCells(
+                            cell_position,
+                        ))?;
+                    } else if cell.is_open() {
+                        // For the rest of the already-open cells, perform the `OpenSurroundingCells` action.
+                        self.perform_action(MinesweeperAction::OpenSurroundingCells(
+                            cell_position,
+                        ))?;
                     } else {
                         // For the closed ones, perform the `OpenCell` action.
-                        self.take_action(MinesweeperAction::OpenCell(cell_position))?;
+                        self.perform_action(MinesweeperAction::OpenCell(cell_position))?;
                     }
                 }
             }
             MinesweeperAction::FlagCell(cell_position) => {
+                self.clicks.flags_amount += 1;
                 self.field.toggle_cell_flag(cell_position);
             }
+            MinesweeperAction::Hint => {
+                self.hint = self.find_hint();
+
+                // The hint taken before the mines are there only tells where the first click is the safest.
+                if self.hint.is_some() && self.field.check_mines_exist() {
+                    self.hints_amount += 1;
+                }
+            }
         };
 
-        self.update_status();
+        Ok(())
+    }
+
+    /// Takes back the most recently applied action, restoring the cells and the status of the game as they were
+    /// before it, and returns the status.
+    ///
+    /// The stopwatch isn't rolled back: the time spent on the undone action is still counted. Using the method makes
+    /// the game assisted.
+    ///
+    /// Fails with [`MinesweeperError::UndoForbidden`] if the game's [`UndoPolicy`] doesn't allow it or with
+    /// [`MinesweeperError::NothingToUndo`] if no actions have been applied yet.
+    pub fn undo(&mut self) -> Result<&MinesweeperStatus, MinesweeperError> {
+        // Don't do anything when the game is paused, just like with any other action.
+        if let MinesweeperStatus::Pause = self.status {
+            return Ok(&MinesweeperStatus::Pause);
+        }
+
+        if !self.check_undo_allowed() {
+            return Err(MinesweeperError::UndoForbidden);
+        }
+
+        let entry = self.history.undo().ok_or(MinesweeperError::NothingToUndo)?;
+        self.hint = None;
+
+        entry.changes.iter().for_each(|change| {
+            self.field
+                .replace_cell(change.position, change.before.clone())
+        });
+        self.status = entry.status_before.clone();
+
+        self.undos_amount += 1;
+        self.sync_stopwatch();
+
+        Ok(&self.status)
+    }
+
+    /// Re-applies the most recently undone action and returns the status of the game.
+    ///
+    /// Fails with [`MinesweeperError::NothingToRedo`] if there are no undone actions or a new action has been taken
+    /// since the last undo.
+    pub fn redo(&mut self) -> Result<&MinesweeperStatus, MinesweeperError> {
+        if let MinesweeperStatus::Pause = self.status {
+            return Ok(&MinesweeperStatus::Pause);
+        }
+
+        let entry = self.history.redo().ok_or(MinesweeperError::NothingToRedo)?;
+        self.hint = None;
+
+        entry.changes.iter().for_each(|change| {
+            self.field
+                .replace_cell(change.position, change.after.clone())
+        });
+        self.status = entry.status_after.clone();
+
+        self.sync_stopwatch();
+
         Ok(&self.status)
     }
 
+    /// Checks whether there's an action that can be undone right now.
+    pub fn can_undo(&self) -> bool {
+        self.status != MinesweeperStatus::Pause
+            && self.check_undo_allowed()
+            && !self.history.get_applied_entries().is_empty()
+    }
+
+    /// Checks whether there's an undone action that can be redone right now.
+    pub fn can_redo(&self) -> bool {
+        self.status != MinesweeperStatus::Pause && !self.history.get_undone_entries().is_empty()
+    }
+
+    /// Returns the number of times an action has been undone during the game.
+    pub fn get_undos_amount(&self) -> u32 {
+        self.undos_amount
+    }
+
+    /// Returns the most recent hint (see [`MinesweeperAction::Hint`]) or [`None`] if the field has changed since then.
+    pub fn get_hint(&self) -> Option<&Hint> {
+        self.hint.as_ref()
+    }
+
+    /// Returns the number of hints used during the game, not counting the ones taken before the field has been
+    /// populated with mines.
+    pub fn get_hints_amount(&self) -> u32 {
+        self.hints_amount
+    }
+
+    /// Checks whether the player has been assisted during the game (i.e., has undone an action or used a hint). The
+    /// assisted games aren't supposed to compete with the regular ones.
+    pub fn is_assisted(&self) -> bool {
+        self.undos_amount > 0 || self.hints_amount > 0
+    }
+
+    /// A private helper that finds the safest cell to open next. Returns [`None`] if there are no closed cells left.
+    fn find_hint(&self) -> Option<Hint> {
+        // Before the field gets populated with mines, the only guarantee is the one of the first click policy.
+        if !self.field.check_mines_exist() {
+            let (rows_amount, columns_amount, _) = self.field.get_size();
+            let position = Position::new(rows_amount / 2, columns_amount / 2);
+
+            return Some(Hint {
+                position,
+                // all the cells are equally likely to be mined (or anti-mined) so far
+                probability: match Field::get_first_click_safe_radius(&self.get_rules()) {
+                    Some(_) => 0.0,
+                    None => calculate_mine_probabilities(&self.field)
+                        .get(position)
+                        .unwrap_or(0.0),
+                },
+            });
+        }
+
+        calculate_mine_probabilities(&self.field)
+            .get_safest_cell()
+            .map(|(position, probability)| Hint {
+                position,
+                probability,
+            })
+    }
+
+    /// A private helper that checks whether the game's undo policy allows to undo an action in the current status.
+    fn check_undo_allowed(&self) -> bool {
+        match self.get_rules().undo {
+            UndoPolicy::Forbidden => false,
+            UndoPolicy::NotAfterLoss => self.status != MinesweeperStatus::End(false),
+            UndoPolicy::Unrestricted => true,
+        }
+    }
+
+    /// A private helper that makes the stopwatch run if and only if the game is on. Should be called after the status
+    /// gets changed by anything other than the regular actions.
+    fn sync_stopwatch(&mut self) {
+        match (&self.status, self.stopwatch.is_running()) {
+            (MinesweeperStatus::On, false) => self.stopwatch.start(),
+            (MinesweeperStatus::Pre | MinesweeperStatus::End(_), true) => self.stopwatch.stop(),
+            _ => (),
+        }
+    }
+
     /// A private helper that updates the game status. Should be called after each action that can potentially change
     /// it.
     fn update_status(&mut self) {
@@ -158,10 +478,10 @@ impl Minesweeper {
         }
     }
 
-    /// The method is a private helper that determines whether the game has been lost or won. If neither (ongoing),
-    /// returns the `None` value.
+    /// The method is a private helper that determines whether the game has been lost (there are no lives left) or won.
+    /// If neither (ongoing), returns the `None` value.
     fn check_victory_or_loss(&self) -> Option<bool> {
-        let loss = self.field.check_open_mines_exist();
+        let loss = self.get_lives_left() == 0;
         let victory = self.field.check_all_non_mines_open();
 
         if loss {
@@ -187,8 +507,585 @@ impl Minesweeper {
         };
     }
 
+    /// Returns the number of mines the player might still hit without losing the game: the number of lives the rules
+    /// give (see [`Rules::lives_amount`]) minus the number of the exploded mines. It's `0` once the game is lost.
+    pub fn get_lives_left(&self) -> u8 {
+        let exploded_cells_amount = self.field.get_exploded_cells_amount();
+
+        (self.get_rules().lives_amount as u32).saturating_sub(exploded_cells_amount) as u8
+    }
+
+    /// Returns the efficiency statistics of the game.
+    ///
+    /// The actions that have been undone are still counted, as they have been taken nonetheless.
+    pub fn get_stats(&self) -> GameStats {
+        GameStats {
+            clicks: self.clicks,
+            metrics: self.field.get_metrics(),
+            time: self.stopwatch.get_elapsed_time(),
+        }
+    }
+
     /// Returns the total amount of time the game has been in the `On` status.
     pub fn get_time(&self) -> u64 {
         self.stopwatch.get_elapsed_time().as_secs()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Minesweeper, MinesweeperAction, MinesweeperError, MinesweeperStatus};
+    use crate::{
+        difficulty::Difficulty,
+        field::{position::Position, Field},
+        rules::{FirstClickPolicy, GenerationMode, Rules, UndoPolicy},
+        stats::ClicksAmounts,
+    };
+
+    fn create_game_with_undo_policy(undo: UndoPolicy) -> Minesweeper {
+        let rules = Rules {
+            undo,
+            ..Default::default()
+        };
+
+        Minesweeper::from_field(
+            Field::from_mine_positions(
+                3,
+                3,
+                &[
+                    Position::new(0, 0),
+                    Position::new(0, 1),
+                    Position::new(1, 2),
+                ],
+                rules,
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn a_game_from_a_mined_field_keeps_the_mines_in_place() {
+        let field = Field::from_mine_positions(
+            3,
+            3,
+            &[
+                Position::new(0, 0),
+                Position::new(0, 1),
+                Position::new(1, 2),
+            ],
+            Rules::default(),
+        )
+        .unwrap();
+        let mut game = Minesweeper::from_field(field);
+
+        assert_eq!(game.get_status(), &MinesweeperStatus::Pre);
+
+        // Opening a mined cell first is a loss, since the field doesn't get re-populated.
+        let status = game
+            .take_action(MinesweeperAction::OpenCell(Position::new(0, 0)))
+            .unwrap();
+        assert_eq!(status, &MinesweeperStatus::End(false));
+    }
+
+    #[test]
+    fn a_game_with_several_lives_goes_on_after_hitting_a_mine() {
+        let rules = Rules {
+            lives_amount: 2,
+            ..Default::default()
+        };
+        let field = Field::from_mine_positions(
+            3,
+            3,
+            &[
+                Position::new(0, 0),
+                Position::new(0, 1),
+                Position::new(1, 2),
+            ],
+            rules,
+        )
+        .unwrap();
+        let mut game = Minesweeper::from_field(field);
+        assert_eq!(game.get_lives_left(), 2);
+
+        // The hit mine stays open as an exploded one, but the game goes on.
+        let status = game
+            .take_action(MinesweeperAction::OpenCell(Position::new(0, 0)))
+            .unwrap();
+        assert_eq!(status, &MinesweeperStatus::On);
+        assert_eq!(game.get_lives_left(), 1);
+        assert!(game
+            .get_field()
+            .get_cell(Position::new(0, 0))
+            .unwrap()
+            .is_exploded());
+
+        // Undoing the hit gives the life back.
+        game.undo().unwrap();
+        assert_eq!(game.get_lives_left(), 2);
+        game.redo().unwrap();
+
+        let status = game
+            .take_action(MinesweeperAction::OpenCell(Position::new(0, 1)))
+            .unwrap();
+        assert_eq!(status, &MinesweeperStatus::End(false));
+        assert_eq!(game.get_lives_left(), 0);
+    }
+
+    #[test]
+    fn a_game_with_several_lives_can_be_won_after_hitting_a_mine() {
+        let rules = Rules {
+            lives_amount: 2,
+            ..Default::default()
+        };
+        let field = Field::from_mine_positions(2, 2, &[Position::new(0, 0)], rules).unwrap();
+        let mut game = Minesweeper::from_field(field);
+
+        for position in [
+            Position::new(0, 0),
+            Position::new(0, 1),
+            Position::new(1, 0),
+        ] {
+            let status = game
+                .take_action(MinesweeperAction::OpenCell(position))
+                .unwrap();
+            assert_eq!(status, &MinesweeperStatus::On);
+        }
+
+        let status = game
+            .take_action(MinesweeperAction::OpenCell(Position::new(1, 1)))
+            .unwrap();
+        assert_eq!(status, &MinesweeperStatus::End(true));
+        assert_eq!(game.get_lives_left(), 1);
+    }
+
+    #[test]
+    fn a_game_from_a_mined_field_can_be_won() {
+        let field = Field::from_mine_positions(
+            3,
+            3,
+            &[
+                Position::new(0, 0),
+                Position::new(0, 1),
+                Position::new(1, 2),
+            ],
+            Rules::default(),
+        )
+        .unwrap();
+        let mut game = Minesweeper::from_field(field);
+
+        for position in [
+            Position::new(0, 2),
+            Position::new(1, 0),
+            Position::new(1, 1),
+            Position::new(2, 0),
+        ] {
+            let status = game
+                .take_action(MinesweeperAction::OpenCell(position))
+                .unwrap();
+            assert_eq!(status, &MinesweeperStatus::On);
+        }
+
+        let status = game
+            .take_action(MinesweeperAction::OpenCell(Position::new(2, 2)))
+            .unwrap();
+        assert_eq!(status, &MinesweeperStatus::End(true));
+    }
+
+    #[test]
+    fn a_game_with_a_preset_difficulty_has_the_preset_size() {
+        let game =
+            Minesweeper::with_difficulty(Difficulty::Expert, Rules::default(), Some(1)).unwrap();
+        let field = game.get_field();
+
+        assert_eq!(field.get_size(), (16, 30, 480));
+        assert_eq!(field.get_mines_amount(), 99);
+    }
+
+    #[test]
+    fn a_game_survives_serialization() {
+        let mut game = Minesweeper::new(9, 9, 10, Rules::default(), Some(42)).unwrap();
+        game.take_action(MinesweeperAction::OpenCell(Position::new(4, 4)))
+            .unwrap();
+        game.take_action(MinesweeperAction::FlagCell(Position::new(0, 0)))
+            .unwrap();
+        game.toggle_pause();
+
+        let deserialized: Minesweeper =
+            serde_json::from_str(&serde_json::to_string(&game).unwrap()).unwrap();
+
+        assert_eq!(deserialized.get_field(), game.get_field());
+        assert_eq!(deserialized.get_status(), &MinesweeperStatus::Pause);
+        assert_eq!(deserialized.get_seed(), 42);
+    }
+
+    #[test]
+    fn undo_restores_the_field_and_the_status() {
+        let mut game = create_game_with_undo_policy(UndoPolicy::NotAfterLoss);
+        let initial_field = game.get_field().clone();
+
+        game.take_action(MinesweeperAction::OpenCell(Position::new(2, 0)))
+            .unwrap();
+        game.take_action(MinesweeperAction::FlagCell(Position::new(0, 0)))
+            .unwrap();
+        assert_eq!(game.get_history().get_applied_entries().len(), 2);
+
+        game.undo().unwrap();
+        assert!(!game
+            .get_field()
+            .get_cell(Position::new(0, 0))
+            .unwrap()
+            .is_flagged());
+        assert!(game
+            .get_field()
+            .get_cell(Position::new(2, 0))
+            .unwrap()
+            .is_open());
+
+        let status = game.undo().unwrap();
+        assert_eq!(status, &MinesweeperStatus::Pre);
+        assert_eq!(game.get_field(), &initial_field);
+        assert!(game.is_assisted());
+        assert_eq!(game.get_undos_amount(), 2);
+
+        assert!(matches!(game.undo(), Err(MinesweeperError::NothingToUndo)));
+    }
+
+    #[test]
+    fn redo_reapplies_the_undone_actions() {
+        let mut game = create_game_with_undo_policy(UndoPolicy::NotAfterLoss);
+
+        game.take_action(MinesweeperAction::OpenCell(Position::new(2, 0)))
+            .unwrap();
+        let field_after_action = game.get_field().clone();
+
+        game.undo().unwrap();
+        let status = game.redo().unwrap();
+        assert_eq!(status, &MinesweeperStatus::On);
+        assert_eq!(game.get_field(), &field_after_action);
+
+        assert!(matches!(game.redo(), Err(MinesweeperError::NothingToRedo)));
+    }
+
+    #[test]
+    fn a_new_action_discards_the_undone_ones() {
+        let mut game = create_game_with_undo_policy(UndoPolicy::NotAfterLoss);
+
+        game.take_action(MinesweeperAction::OpenCell(Position::new(2, 0)))
+            .unwrap();
+        game.undo().unwrap();
+        game.take_action(MinesweeperAction::OpenCell(Position::new(0, 2)))
+            .unwrap();
+
+        assert!(!game.can_redo());
+    }
+
+    #[test]
+    fn the_actions_without_effect_are_not_recorded() {
+        let mut game = create_game_with_undo_policy(UndoPolicy::NotAfterLoss);
+
+        game.take_action(MinesweeperAction::OpenCell(Position::new(2, 0)))
+            .unwrap();
+        game.take_action(MinesweeperAction::OpenCell(Position::new(2, 0)))
+            .unwrap();
+
+        assert_eq!(game.get_history().get_applied_entries().len(), 1);
+    }
+
+    #[test]
+    fn the_history_only_keeps_the_changed_cells() {
+        let mut game = create_game_with_undo_policy(UndoPolicy::NotAfterLoss);
+
+        game.take_action(MinesweeperAction::OpenCell(Position::new(2, 0)))
+            .unwrap();
+        game.take_action(MinesweeperAction::FlagCell(Position::new(0, 0)))
+            .unwrap();
+
+        let entries = game.get_history().get_applied_entries();
+        // the corner cell is an opening, so its three neighbours get opened along with it
+        assert_eq!(entries[0].changes.len(), 4);
+        assert_eq!(entries[1].changes.len(), 1);
+        assert_eq!(entries[1].changes[0].position, Position::new(0, 0));
+        assert!(entries[1].changes[0].after.is_flagged());
+    }
+
+    #[test]
+    fn the_history_is_not_kept_when_undo_is_forbidden() {
+        let mut game = create_game_with_undo_policy(UndoPolicy::Forbidden);
+
+        game.take_action(MinesweeperAction::OpenCell(Position::new(2, 0)))
+            .unwrap();
+        game.take_action(MinesweeperAction::FlagCell(Position::new(0, 0)))
+            .unwrap();
+        game.take_action(MinesweeperAction::OpenCell(Position::new(2, 0)))
+            .unwrap();
+
+        assert!(game.get_history().get_applied_entries().is_empty());
+        assert_eq!(game.get_stats().clicks.wasted_clicks_amount, 1);
+    }
+
+    #[test]
+    fn undo_respects_the_undo_policy() {
+        let mut game = create_game_with_undo_policy(UndoPolicy::Forbidden);
+        game.take_action(MinesweeperAction::FlagCell(Position::new(0, 0)))
+            .unwrap();
+        assert!(!game.can_undo());
+        assert!(matches!(game.undo(), Err(MinesweeperError::UndoForbidden)));
+
+        let mut game = create_game_with_undo_policy(UndoPolicy::NotAfterLoss);
+        game.take_action(MinesweeperAction::OpenCell(Position::new(0, 0)))
+            .unwrap();
+        assert!(matches!(game.undo(), Err(MinesweeperError::UndoForbidden)));
+        assert!(!game.is_assisted());
+
+        let mut game = create_game_with_undo_policy(UndoPolicy::Unrestricted);
+        game.take_action(MinesweeperAction::OpenCell(Position::new(2, 0)))
+            .unwrap();
+        game.take_action(MinesweeperAction::OpenCell(Position::new(0, 0)))
+            .unwrap();
+        let status = game.undo().unwrap();
+        assert_eq!(status, &MinesweeperStatus::On);
+        assert!(!game
+            .get_field()
+            .get_cell(Position::new(0, 0))
+            .unwrap()
+            .is_open());
+        assert!(!game.get_field().check_open_mines_exist());
+    }
+
+    #[test]
+    fn huge_fields_are_playable() {
+        let mut game = Minesweeper::new(300, 300, 20_000, Rules::default(), Some(42)).unwrap();
+
+        game.take_action(MinesweeperAction::OpenCell(Position::new(280, 290)))
+            .unwrap();
+        game.take_action(MinesweeperAction::FlagCell(Position::new(299, 0)))
+            .unwrap();
+
+        assert_eq!(game.get_status(), &MinesweeperStatus::On);
+        assert!(game
+            .get_field()
+            .get_cell(Position::new(280, 290))
+            .unwrap()
+            .is_open());
+        assert_eq!(game.get_field().get_mines_amount(), 20_000);
+    }
+
+    #[test]
+    fn undoing_the_first_move_allows_for_a_different_field() {
+        let mut game = Minesweeper::new(9, 9, 10, Rules::default(), Some(7)).unwrap();
+
+        game.take_action(MinesweeperAction::OpenCell(Position::new(0, 0)))
+            .unwrap();
+        game.undo().unwrap();
+
+        // The mines are taken back along with the first move, so the field gets populated again.
+        assert_eq!(game.get_status(), &MinesweeperStatus::Pre);
+        assert!(!game.get_field().check_mines_exist());
+
+        game.take_action(MinesweeperAction::OpenCell(Position::new(8, 8)))
+            .unwrap();
+        assert!(!game
+            .get_field()
+            .get_cell(Position::new(8, 8))
+            .unwrap()
+            .is_mined());
+    }
+
+    #[test]
+    fn a_hint_before_the_first_move_accounts_for_the_no_guess_generation() {
+        let rules = Rules {
+            first_click: FirstClickPolicy::NoProtection,
+            generation: GenerationMode::NoGuess,
+            ..Default::default()
+        };
+        let mut game = Minesweeper::new(9, 9, 10, rules, Some(42)).unwrap();
+
+        game.take_action(MinesweeperAction::Hint).unwrap();
+
+        let hint = game.get_hint().unwrap();
+        assert_eq!(hint.position, Position::new(4, 4));
+        assert_eq!(hint.probability, 0.0);
+        // the hint doesn't reveal anything about the mines, so it doesn't make the game assisted
+        assert_eq!(game.get_hints_amount(), 0);
+        assert!(!game.is_assisted());
+    }
+
+    #[test]
+    fn a_hint_suggests_a_provably_safe_cell() {
+        // The cell in the middle of the right column is the only closed cell which isn't mined.
+        let field = Field::from_mine_positions(
+            3,
+            4,
+            &[Position::new(0, 3), Position::new(2, 3)],
+            Rules::default(),
+        )
+        .unwrap();
+        let mut game = Minesweeper::from_field(field);
+        game.take_action(MinesweeperAction::OpenCell(Position::new(1, 0)))
+            .unwrap();
+
+        game.take_action(MinesweeperAction::Hint).unwrap();
+
+        let hint = game.get_hint().unwrap();
+        assert_eq!(hint.position, Position::new(1, 3));
+        assert_eq!(hint.probability, 0.0);
+    }
+
+    #[test]
+    fn a_hint_suggests_the_least_risky_cell_when_there_are_no_safe_ones() {
+        // The 1 has a single mine among its three neighbours, while the other mine is somewhere among the four other
+        // cells, so the cells next to the 1 are riskier.
+        //
+        // 1 . . .
+        // . . . .
+        let field = Field::from_mine_positions(
+            2,
+            4,
+            &[Position::new(1, 1), Position::new(1, 3)],
+            Rules::default(),
+        )
+        .unwrap();
+        let mut game = Minesweeper::from_field(field);
+        game.take_action(MinesweeperAction::OpenCell(Position::new(0, 0)))
+            .unwrap();
+
+        game.take_action(MinesweeperAction::Hint).unwrap();
+
+        let hint = game.get_hint().unwrap();
+        assert_eq!(hint.position, Position::new(0, 2));
+        assert!((hint.probability - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn hints_are_counted_and_make_the_game_assisted() {
+        let mut game = create_game_with_undo_policy(UndoPolicy::NotAfterLoss);
+        game.take_action(MinesweeperAction::OpenCell(Position::new(2, 0)))
+            .unwrap();
+        assert!(!game.is_assisted());
+
+        game.take_action(MinesweeperAction::Hint).unwrap();
+        assert_eq!(game.get_hints_amount(), 1);
+        assert!(game.is_assisted());
+
+        // The hint is not an action that can be undone, and it's forgotten as soon as the field changes.
+        assert_eq!(game.get_history().get_applied_entries().len(), 1);
+        game.take_action(MinesweeperAction::FlagCell(Position::new(0, 0)))
+            .unwrap();
+        assert!(game.get_hint().is_none());
+    }
+
+    #[test]
+    fn the_actions_are_counted_in_the_stats() {
+        let mut game = create_game_with_undo_policy(UndoPolicy::NotAfterLoss);
+
+        game.take_action(MinesweeperAction::OpenCell(Position::new(2, 0)))
+            .unwrap();
+        // Opening an already open cell changes nothing.
+        game.take_action(MinesweeperAction::OpenCell(Position::new(2, 0)))
+            .unwrap();
+        // So does chording a number that isn't surrounded by enough flags.
+        game.take_action(MinesweeperAction::OpenSurroundingCells(Position::new(2, 1)))
+            .unwrap();
+        game.take_action(MinesweeperAction::FlagCell(Position::new(1, 2)))
+            .unwrap();
+        game.take_action(MinesweeperAction::OpenSurroundingCells(Position::new(2, 1)))
+            .unwrap();
+        // Hints aren't clicks.
+        game.take_action(MinesweeperAction::Hint).unwrap();
+        let status = game
+            .take_action(MinesweeperAction::OpenCell(Position::new(0, 2)))
+            .unwrap();
+        assert_eq!(status, &MinesweeperStatus::End(true));
+
+        let stats = game.get_stats();
+        assert_eq!(
+            stats.clicks,
+            ClicksAmounts {
+                left_clicks_amount: 3,
+                chords_amount: 2,
+                flags_amount: 1,
+                wasted_clicks_amount: 2,
+            }
+        );
+        assert_eq!(stats.metrics.three_bv, 3);
+        assert_eq!(stats.metrics.solved_three_bv, 3);
+        assert_eq!(stats.get_ioe(), 0.5);
+        assert_eq!(stats.get_correctness(), 4.0 / 6.0);
+    }
+
+    #[test]
+    fn the_actions_beyond_the_field_are_not_counted_as_wasted_clicks() {
+        let mut game = create_game_with_undo_policy(UndoPolicy::NotAfterLoss);
+
+        game.take_action(MinesweeperAction::FlagCell(Position::new(0, 0)))
+            .unwrap();
+        game.take_action(MinesweeperAction::OpenCellOrSurroundingCells(
+            Position::new(5, 5),
+        ))
+        .unwrap();
+        game.take_action(MinesweeperAction::OpenCellOrSurroundingCells(
+            Position::new(5, 5),
+        ))
+        .unwrap();
+
+        let stats = game.get_stats();
+        assert_eq!(stats.get_clicks_amount(), 1);
+        assert_eq!(stats.clicks.wasted_clicks_amount, 0);
+        assert_eq!(stats.get_correctness(), 1.0);
+    }
+
+    #[test]
+    fn open_cell_or_surrounding_cells_might_flag_the_surrounding_cells() {
+        // * * 2
+        // 2 3 *
+        // 0 1 1
+        let create_game = |auto_flag| {
+            let rules = Rules {
+                auto_flag,
+                ..Default::default()
+            };
+            let mut game = Minesweeper::from_field(
+                Field::from_mine_positions(
+                    3,
+                    3,
+                    &[
+                        Position::new(0, 0),
+                        Position::new(0, 1),
+                        Position::new(1, 2),
+                    ],
+                    rules,
+                )
+                .unwrap(),
+            );
+            game.take_action(MinesweeperAction::OpenCell(Position::new(2, 0)))
+                .unwrap();
+
+            game
+        };
+
+        // The 2 on the left only has two closed cells around it.
+        let mut game = create_game(true);
+        game.take_action(MinesweeperAction::OpenCellOrSurroundingCells(
+            Position::new(1, 0),
+        ))
+        .unwrap();
+        assert_eq!(game.get_field().get_flagged_cells_amount(), 2);
+        assert_eq!(game.get_stats().clicks.chords_amount, 1);
+
+        // With both of them flagged, the same action opens the surrounding cells instead (there are none left).
+        game.take_action(MinesweeperAction::OpenCellOrSurroundingCells(
+            Position::new(1, 0),
+        ))
+        .unwrap();
+        assert_eq!(game.get_stats().clicks.chords_amount, 2);
+        assert_eq!(game.get_stats().clicks.wasted_clicks_amount, 1);
+
+        // Without the rule, the cells are never flagged automatically.
+        let mut game = create_game(false);
+        game.take_action(MinesweeperAction::OpenCellOrSurroundingCells(
+            Position::new(1, 0),
+        ))
+        .unwrap();
+        assert_eq!(game.get_field().get_flagged_cells_amount(), 0);
+    }
+}
@@ -0,0 +1,343 @@
+//! The rules a game is played by.
+//!
+//! All the rules have sensible defaults (those of the classic Minesweeper), so that only the ones that differ need to
+//! be specified, e.g. `Rules { generation: GenerationMode::NoGuess, ..Default::default() }`.
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// The largest value of [`Rules::max_mines_per_cell`]. Even with the [`Neighborhood::Extended`] neighbourhood, the
+/// numbers stay small enough to fit into a byte.
+pub const MAX_MINES_PER_CELL: u8 = 9;
+
+/// The way the mines get distributed across the field.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GenerationMode {
+    /// The mines are distributed completely randomly. The player might have to guess at some point.
+    #[default]
+    Random,
+    /// Only the layouts that can be fully solved by logic starting from the first opened cell are accepted.
+    ///
+    /// Regardless of the [`FirstClickPolicy`], the first opened cell is also guaranteed to be at least an opening
+    /// (i.e., to have no mines around it), which is why the mode might allow for fewer mines than the random one.
+    NoGuess,
+}
+
+/// What's guaranteed to the player about their first move.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FirstClickPolicy {
+    /// Nothing's guaranteed: the first opened cell might as well be mined.
+    NoProtection,
+    /// The first opened cell is guaranteed not to be mined.
+    #[default]
+    SafeCell,
+    /// Neither the first opened cell nor its neighbours are mined, so the first move always opens an area.
+    Opening,
+    /// None of the cells within the given radius from the first opened cell are mined. The radius is measured in steps
+    /// between the neighbouring cells (see [`Neighborhood`]): the radius of 1 is equivalent to
+    /// [`FirstClickPolicy::Opening`], and with the default neighbourhood on the square grid the radius of 2 covers a
+    /// 5x5 square area, and so on. On the hexagonal grid the area is a hexagon.
+    SafeArea(u8),
+}
+
+impl FirstClickPolicy {
+    /// Returns the radius of the area around the first opened cell that's kept free of mines (`0` meaning the
+    /// cell itself only) or [`None`] if nothing's guaranteed.
+    pub fn get_safe_radius(&self) -> Option<u8> {
+        match self {
+            FirstClickPolicy::NoProtection => None,
+            FirstClickPolicy::SafeCell => Some(0),
+            FirstClickPolicy::Opening => Some(1),
+            FirstClickPolicy::SafeArea(radius) => Some(*radius),
+        }
+    }
+}
+
+/// Parses the policy from either of `none`, `safe-cell`, `opening` or a number, which is treated as the radius of the
+/// safe area.
+impl FromStr for FirstClickPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(FirstClickPolicy::NoProtection),
+            "safe-cell" => Ok(FirstClickPolicy::SafeCell),
+            "opening" => Ok(FirstClickPolicy::Opening),
+            _ => s.parse().map(FirstClickPolicy::SafeArea).map_err(|_| {
+                format!("expected `none`, `safe-cell`, `opening` or a radius, got `{s}`")
+            }),
+        }
+    }
+}
+
+/// When the player is allowed to take their actions back.
+///
+/// Regardless of the policy, a game where at least one action has been undone is considered assisted (see
+/// [`crate::Minesweeper::is_assisted`]).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UndoPolicy {
+    /// No action can be undone.
+    Forbidden,
+    /// Any action can be undone while the game is on. A lost game can't be brought back to life, though.
+    #[default]
+    NotAfterLoss,
+    /// Any action can be undone, including the one that has lost the game.
+    Unrestricted,
+}
+
+/// Parses the policy from either of `forbidden`, `not-after-loss` or `unrestricted`.
+impl FromStr for UndoPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "forbidden" => Ok(UndoPolicy::Forbidden),
+            "not-after-loss" => Ok(UndoPolicy::NotAfterLoss),
+            "unrestricted" => Ok(UndoPolicy::Unrestricted),
+            _ => Err(format!(
+                "expected `forbidden`, `not-after-loss` or `unrestricted`, got `{s}`"
+            )),
+        }
+    }
+}
+
+/// The shape of the field, which determines what cells are adjacent to each other.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Topology {
+    /// The classic flat field: the cells at the edges have fewer neighbours.
+    #[default]
+    Plane,
+    /// The edges wrap around (the top one is adjacent to the bottom one, the left one is adjacent to the right one),
+    /// so that every cell has the full set of neighbours (as long as the field is at least 3x3).
+    ///
+    /// On the [`Grid::Hexagonal`] grid, the number of rows must be even for the shifted rows to line up across the top
+    /// and bottom edges.
+    Torus,
+}
+
+/// Parses the topology from either of `plane` or `torus`.
+impl FromStr for Topology {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plane" => Ok(Topology::Plane),
+            "torus" => Ok(Topology::Torus),
+            _ => Err(format!("expected `plane` or `torus`, got `{s}`")),
+        }
+    }
+}
+
+/// The shape of the cells, which determines how many neighbours each of them has.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Grid {
+    /// The classic grid of square cells, each having 8 neighbours (including the diagonal ones).
+    #[default]
+    Square,
+    /// The grid of hexagonal cells, each having 6 neighbours. The cells are still laid out in rows and columns, but
+    /// every odd row is shifted to the right by half a cell, so that the cells of the adjacent rows interlock.
+    Hexagonal,
+}
+
+/// The offsets (in rows and columns) of the cells adjacent to any cell of the square grid.
+const SQUARE_NEIGHBOUR_OFFSETS: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+/// The offsets of the cells adjacent to a cell of an even row of the hexagonal grid. The neighbours in the rows above
+/// and below are the cell with the same column index and the one to the left of it.
+const EVEN_ROW_HEXAGONAL_NEIGHBOUR_OFFSETS: [(i32, i32); 6] =
+    [(-1, -1), (-1, 0), (0, -1), (0, 1), (1, -1), (1, 0)];
+/// The offsets of the cells adjacent to a cell of an odd (shifted) row of the hexagonal grid. The neighbours in the
+/// rows above and below are the cell with the same column index and the one to the right of it.
+const ODD_ROW_HEXAGONAL_NEIGHBOUR_OFFSETS: [(i32, i32); 6] =
+    [(-1, 0), (-1, 1), (0, -1), (0, 1), (1, 0), (1, 1)];
+
+impl Grid {
+    /// Returns the offsets (in rows and columns) of the cells adjacent to a cell of the row with the provided index, in
+    /// the row-major order. The offsets don't account for the field's edges.
+    pub fn get_neighbour_offsets(&self, row_index: u16) -> &'static [(i32, i32)] {
+        match self {
+            Grid::Square => &SQUARE_NEIGHBOUR_OFFSETS,
+            Grid::Hexagonal if row_index.is_multiple_of(2) => &EVEN_ROW_HEXAGONAL_NEIGHBOUR_OFFSETS,
+            Grid::Hexagonal => &ODD_ROW_HEXAGONAL_NEIGHBOUR_OFFSETS,
+        }
+    }
+}
+
+/// Parses the grid from either of `square` or `hexagonal`.
+impl FromStr for Grid {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "square" => Ok(Grid::Square),
+            "hexagonal" => Ok(Grid::Hexagonal),
+            _ => Err(format!("expected `square` or `hexagonal`, got `{s}`")),
+        }
+    }
+}
+
+/// The cells that the number of a cell counts the mines among. The same cells get opened around an opening and when
+/// chording (opening the surrounding cells of a cell).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Neighborhood {
+    /// The cells adjacent to the cell: 8 on the square grid and 6 on the hexagonal one.
+    #[default]
+    Adjacent,
+    /// Only the cells sharing a side with the cell: 4 on the square grid. All the adjacent cells of the hexagonal grid
+    /// share a side, so there it's the same as [`Neighborhood::Adjacent`].
+    Orthogonal,
+    /// The cells a chess knight's move away from the cell: 8 on the square grid. The neighbourhood is not defined for
+    /// the hexagonal grid.
+    KnightMove,
+    /// The cells up to two steps away from the cell: 24 on the square grid (a 5x5 square) and 18 on the hexagonal one.
+    Extended,
+}
+
+/// The offsets of the cells sharing a side with any cell of the square grid.
+const ORTHOGONAL_NEIGHBOUR_OFFSETS: [(i32, i32); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+/// The offsets of the cells a knight's move away from any cell of the square grid.
+const KNIGHT_MOVE_NEIGHBOUR_OFFSETS: [(i32, i32); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+
+impl Neighborhood {
+    /// Returns the offsets (in rows and columns) of the neighbours of a cell of the row with the provided index on the
+    /// provided grid, in the row-major order, or [`None`] if the neighbourhood is not defined for the grid. The offsets
+    /// don't account for the field's edges.
+    pub fn get_offsets(&self, grid: Grid, row_index: u16) -> Option<Vec<(i32, i32)>> {
+        match (self, grid) {
+            (Neighborhood::Adjacent, _) | (Neighborhood::Orthogonal, Grid::Hexagonal) => {
+                Some(grid.get_neighbour_offsets(row_index).to_vec())
+            }
+            (Neighborhood::Orthogonal, Grid::Square) => Some(ORTHOGONAL_NEIGHBOUR_OFFSETS.to_vec()),
+            (Neighborhood::KnightMove, Grid::Square) => {
+                Some(KNIGHT_MOVE_NEIGHBOUR_OFFSETS.to_vec())
+            }
+            (Neighborhood::KnightMove, Grid::Hexagonal) => None,
+            (Neighborhood::Extended, _) => {
+                // The adjacent cells along with the ones adjacent to them. On the hexagonal grid, the offsets of the
+                // second step depend on the row reached by the first one (only its parity matters, so wrapping is
+                // fine).
+                let mut offsets = grid
+                    .get_neighbour_offsets(row_index)
+                    .iter()
+                    .flat_map(|&(row_offset, column_offset)| {
+                        let next_row_index = row_index.wrapping_add_signed(row_offset as i16);
+
+                        grid.get_neighbour_offsets(next_row_index)
+                            .iter()
+                            .map(move |&(next_row_offset, next_column_offset)| {
+                                (
+                                    row_offset + next_row_offset,
+                                    column_offset + next_column_offset,
+                                )
+                            })
+                            .chain([(row_offset, column_offset)])
+                    })
+                    .filter(|&offset| offset != (0, 0))
+                    .collect::<Vec<(i32, i32)>>();
+
+                offsets.sort();
+                offsets.dedup();
+
+                Some(offsets)
+            }
+        }
+    }
+}
+
+/// Parses the neighbourhood from either of `adjacent`, `orthogonal`, `knight-move` or `extended`.
+impl FromStr for Neighborhood {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "adjacent" => Ok(Neighborhood::Adjacent),
+            "orthogonal" => Ok(Neighborhood::Orthogonal),
+            "knight-move" => Ok(Neighborhood::KnightMove),
+            "extended" => Ok(Neighborhood::Extended),
+            _ => Err(format!(
+                "expected `adjacent`, `orthogonal`, `knight-move` or `extended`, got `{s}`"
+            )),
+        }
+    }
+}
+
+/// The set of rules a game is played by.
+///
+/// The rules missing from the serialized representation (e.g., the ones that have been introduced after a game was
+/// saved) take their default values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rules {
+    /// The way the mines get distributed across the field.
+    pub generation: GenerationMode,
+    /// What's guaranteed to the player about their first move.
+    pub first_click: FirstClickPolicy,
+    /// When the player is allowed to take their actions back.
+    pub undo: UndoPolicy,
+    /// The shape of the field.
+    pub topology: Topology,
+    /// The shape of the cells.
+    pub grid: Grid,
+    /// The cells that the numbers count the mines among.
+    pub neighborhood: Neighborhood,
+    /// The maximum number of mines a single cell might hold, from `1` (the classic game) to [`MAX_MINES_PER_CELL`].
+    ///
+    /// The numbers count the mines rather than the mined cells, and the flags of a closed cell cycle from one up to
+    /// this number.
+    pub max_mines_per_cell: u8,
+    /// The number of anti-mines hidden in the field along with the mines (`0` for the classic game).
+    ///
+    /// An anti-mine is just as deadly as a mine, but it counts as `-1` towards the numbers around it, so that a number
+    /// might be negative, or even zero while there are mines around. The anti-mines are marked with anti-flags, which
+    /// count as `-1` when chording.
+    pub anti_mines_amount: u32,
+    /// Whether the marks of a closed cell cycle through a question mark before being removed (`false` for the classic
+    /// flag and no flag cycling).
+    ///
+    /// A question mark is nothing but a note for the player: it's not counted as a flag and doesn't prevent the cell
+    /// from being opened.
+    pub question_marks: bool,
+    /// Whether [`crate::MinesweeperAction::OpenCellOrSurroundingCells`] also flags the closed cells around an open
+    /// number when they all have to be mined (`false` for the classic game). Otherwise, it only opens them.
+    pub auto_flag: bool,
+    /// The number of mines the player might hit before losing the game, at least `1` (the classic game).
+    ///
+    /// As long as there are lives left, a hit mine stays open as an exploded one and the game goes on. The exploded
+    /// mines count as flagged when chording.
+    pub lives_amount: u8,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            generation: GenerationMode::default(),
+            first_click: FirstClickPolicy::default(),
+            undo: UndoPolicy::default(),
+            topology: Topology::default(),
+            grid: Grid::default(),
+            neighborhood: Neighborhood::default(),
+            max_mines_per_cell: 1,
+            anti_mines_amount: 0,
+            question_marks: false,
+            auto_flag: false,
+            lives_amount: 1,
+        }
+    }
+}
@@ -0,0 +1,504 @@
+//! The logical solver.
+//!
+//! The solver only relies on the information visible to the player: the numbers of the open cells, the positions of
+//! the closed ones and the total number of mines. It never peeks at the contents of the closed cells, so it can be
+//! used to power hints, bots and board analysis, as well as to check whether a field can be solved without guessing.
+//! The flags are not trusted, since they are nothing but the player's guesses.
+//!
+//! If the rules allow for several mines per cell, a cell is only considered resolved once its exact number of mines is
+//! known. If the rules require anti-mines, each of them counts as -1 mine, so a number of 0 doesn't mean there are no
+//! mines around. The solver doesn't take advantage of whether the cells around an open 0 have been opened
+//! automatically or not.
+//!
+//! ```
+//! use mine_rs::{field::{position::Position, Field}, rules::Rules, solver};
+//!
+//! let mut field = Field::from_mine_positions(3, 3, &[Position::new(0, 0)], Rules::default()).unwrap();
+//! field.open_cell(Position::new(2, 2));
+//!
+//! // The only closed cell left must be the mined one.
+//! let deductions = solver::deduce(&field);
+//! assert!(deductions.mined.contains(&Position::new(0, 0)));
+//! assert!(deductions.safe.is_empty());
+//! ```
+
+pub mod probability;
+
+use crate::field::{position::Position, Field};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// The closed cells the solver has managed to prove to be safe or mined.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Deductions {
+    /// The positions of the cells that are guaranteed not to contain a mine.
+    pub safe: BTreeSet<Position>,
+    /// The positions of the cells that are guaranteed to contain a mine (and, if the rules allow for several mines per
+    /// cell, whose exact number of mines is known) or an anti-mine.
+    pub mined: BTreeSet<Position>,
+}
+
+/// The closed cells the solver has managed to resolve, along with the numbers of mines in the mined ones.
+#[derive(Debug, Default)]
+struct Resolution {
+    /// The positions of the cells that are guaranteed not to contain a mine.
+    safe: BTreeSet<Position>,
+    /// The exact numbers of mines of the cells that are guaranteed to contain some, where an anti-mine is -1.
+    mines_amounts: BTreeMap<Position, i32>,
+}
+
+impl Resolution {
+    /// Resolves the cell with the given position as holding the given number of mines (where -1 is an anti-mine).
+    fn insert(&mut self, position: Position, mines_amount: i32) {
+        if mines_amount == 0 {
+            self.safe.insert(position);
+        } else {
+            self.mines_amounts.insert(position, mines_amount);
+        }
+    }
+
+    /// Returns the number of the resolved cells.
+    fn len(&self) -> usize {
+        self.safe.len() + self.mines_amounts.len()
+    }
+
+    /// Checks whether the cell with the given position is resolved.
+    fn contains(&self, position: &Position) -> bool {
+        self.safe.contains(position) || self.mines_amounts.contains_key(position)
+    }
+}
+
+/// A single piece of knowledge about the field: exactly `mines_amount` of the `cells` are mined.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Constraint {
+    /// The positions of the yet-unresolved cells the constraint is about. Always sorted.
+    cells: Vec<Position>,
+    /// The number of mines among the cells, where an anti-mine counts as -1.
+    mines_amount: i32,
+}
+
+/// Finds all the closed cells of the field that are provably safe or mined.
+///
+/// The deductions are made by repeatedly applying the single-point reasoning (a number is already satisfied or is only
+/// satisfiable by mining all of its closed neighbours) and the pairwise one (comparing two overlapping numbers, where
+/// the difference of their values is only explainable by a particular distribution of the mines). The total number of
+/// mines is taken into account as well, as a constraint over all the unresolved cells of the field.
+pub fn deduce(field: &Field) -> Deductions {
+    let Resolution {
+        safe,
+        mines_amounts,
+    } = resolve(field);
+
+    Deductions {
+        safe,
+        mined: mines_amounts.into_keys().collect(),
+    }
+}
+
+/// Resolves as many closed cells of the field as possible (see [`deduce`]).
+fn resolve(field: &Field) -> Resolution {
+    let max_mines_per_cell = field.get_rules().max_mines_per_cell as i32;
+    let mut resolution = Resolution::default();
+
+    loop {
+        // The range of the numbers of mines a single cell might hold, where an anti-mine is -1. Once all the anti-mines
+        // are found, the rest of the cells can't hold any.
+        let min_mines_per_cell =
+            if count_known_anti_mines(field, &resolution) < field.get_anti_mines_amount() {
+                -1
+            } else {
+                0
+            };
+
+        let constraints = collect_constraints(field, &resolution);
+        let new_resolution =
+            apply_constraints(&constraints, min_mines_per_cell, max_mines_per_cell);
+
+        let known_cells_amount = resolution.len();
+        resolution.safe.extend(new_resolution.safe);
+        resolution
+            .mines_amounts
+            .extend(new_resolution.mines_amounts);
+
+        // Keep going until nothing new can be deduced.
+        if resolution.len() == known_cells_amount {
+            return resolution;
+        }
+    }
+}
+
+/// Counts the anti-mines known to the player: the resolved ones and the open ones (e.g., the one that has been hit).
+fn count_known_anti_mines(field: &Field, resolution: &Resolution) -> u32 {
+    let (rows_amount, columns_amount, _) = field.get_size();
+
+    let open_anti_mines_amount = (0..rows_amount)
+        .flat_map(|row_index| {
+            (0..columns_amount).map(move |column_index| Position::new(row_index, column_index))
+        })
+        .filter_map(|position| field.get_cell(position))
+        .filter(|cell| cell.is_open() && cell.is_anti_mined())
+        .count();
+    let resolved_anti_mines_amount = resolution
+        .mines_amounts
+        .values()
+        .filter(|mines_amount| **mines_amount < 0)
+        .count();
+
+    (open_anti_mines_amount + resolved_anti_mines_amount) as u32
+}
+
+/// Builds a constraint for each of the open numbered cells that still have unresolved cells around them, plus the
+/// global one stating how many mines are left among all the unresolved cells.
+fn collect_constraints(field: &Field, resolution: &Resolution) -> Vec<Constraint> {
+    let mut constraints = collect_number_constraints(field, resolution);
+    constraints.extend(collect_global_constraint(field, resolution));
+
+    // Several numbers might be describing the very same cells. There's no need to consider them more than once.
+    constraints.sort();
+    constraints.dedup();
+
+    constraints
+}
+
+/// Builds a constraint for each of the open numbered cells that still have unresolved cells around them.
+fn collect_number_constraints(field: &Field, resolution: &Resolution) -> Vec<Constraint> {
+    let (rows_amount, columns_amount, _) = field.get_size();
+
+    let mut constraints = (0..rows_amount)
+        .flat_map(|row_index| {
+            (0..columns_amount).map(move |column_index| Position::new(row_index, column_index))
+        })
+        .filter_map(|position| {
+            let cell = field.get_cell(position)?;
+
+            if !cell.is_open() {
+                return None;
+            }
+
+            let mines_around_amount = cell.get_mines_around_amount()?;
+            let mut cells = Vec::new();
+            let mut known_mines_amount = 0;
+
+            field
+                .get_adjacent_positions(position)
+                .into_iter()
+                .filter_map(|adjacent_cell_position| {
+                    field
+                        .get_cell(adjacent_cell_position)
+                        .map(|adjacent_cell| (adjacent_cell_position, adjacent_cell))
+                })
+                .for_each(|(adjacent_cell_position, adjacent_cell)| {
+                    if let Some(mines_amount) =
+                        resolution.mines_amounts.get(&adjacent_cell_position)
+                    {
+                        known_mines_amount += mines_amount;
+                    } else if adjacent_cell.is_open() {
+                        // an open mine is visible to the player along with the number of mines in it
+                        known_mines_amount += adjacent_cell.get_signed_mines_amount() as i32;
+                    } else if !resolution.safe.contains(&adjacent_cell_position) {
+                        cells.push(adjacent_cell_position);
+                    }
+                });
+
+            if cells.is_empty() {
+                None
+            } else {
+                cells.sort();
+
+                Some(Constraint {
+                    cells,
+                    mines_amount: mines_around_amount as i32 - known_mines_amount,
+                })
+            }
+        })
+        .collect::<Vec<Constraint>>();
+
+    // Several numbers might be describing the very same cells. There's no need to consider them more than once.
+    constraints.sort();
+    constraints.dedup();
+
+    constraints
+}
+
+/// Builds the constraint stating that all the mines (and anti-mines) that are not yet known to the player are among the
+/// unresolved cells, or returns [`None`] if there are no unresolved cells left.
+fn collect_global_constraint(field: &Field, resolution: &Resolution) -> Option<Constraint> {
+    let (rows_amount, columns_amount, _) = field.get_size();
+    let mut cells = Vec::new();
+    let mut known_mines_amount = resolution.mines_amounts.values().sum::<i32>();
+
+    for position in (0..rows_amount).flat_map(|row_index| {
+        (0..columns_amount).map(move |column_index| Position::new(row_index, column_index))
+    }) {
+        let Some(cell) = field.get_cell(position) else {
+            continue;
+        };
+
+        if cell.is_open() {
+            // an open mine is visible to the player (e.g., the one that has been hit)
+            known_mines_amount += cell.get_signed_mines_amount() as i32;
+        } else if !resolution.contains(&position) {
+            cells.push(position);
+        }
+    }
+
+    if cells.is_empty() {
+        None
+    } else {
+        // The positions are generated row by row, so they are sorted already.
+        Some(Constraint {
+            cells,
+            mines_amount: field.get_mines_amount() as i32
+                - field.get_anti_mines_amount() as i32
+                - known_mines_amount,
+        })
+    }
+}
+
+/// Applies the single-point and the pairwise reasoning to the constraints and returns the cells which are proven to be
+/// safe or mined, given that a cell might hold from `min_mines_per_cell` (-1 being an anti-mine) to
+/// `max_mines_per_cell` mines.
+fn apply_constraints(
+    constraints: &[Constraint],
+    min_mines_per_cell: i32,
+    max_mines_per_cell: i32,
+) -> Resolution {
+    let mut resolution = Resolution::default();
+
+    // Single-point: the constraint is only satisfiable by filling all of its cells with either the least or the most
+    // mines possible (the former meaning they are safe unless there are anti-mines). A constraint about a single cell
+    // tells its exact number of mines.
+    for constraint in constraints {
+        let cells_amount = constraint.cells.len() as i32;
+
+        if constraint.mines_amount == min_mines_per_cell * cells_amount {
+            for cell_position in constraint.cells.iter() {
+                resolution.insert(*cell_position, min_mines_per_cell);
+            }
+        } else if constraint.mines_amount == max_mines_per_cell * cells_amount {
+            for cell_position in constraint.cells.iter() {
+                resolution.insert(*cell_position, max_mines_per_cell);
+            }
+        } else if let [cell_position] = constraint.cells[..] {
+            resolution.insert(cell_position, constraint.mines_amount);
+        }
+    }
+
+    // Index the constraints by the cells they mention to only compare the ones that overlap.
+    let mut constraints_by_cell: HashMap<Position, Vec<usize>> = HashMap::new();
+    for (index, constraint) in constraints.iter().enumerate() {
+        for cell_position in constraint.cells.iter() {
+            constraints_by_cell
+                .entry(*cell_position)
+                .or_default()
+                .push(index);
+        }
+    }
+
+    // Pairwise: if the difference in the amounts of mines of two overlapping constraints `a` and `b` is equal to the
+    // most it might be (when the cells that only belong to `b` hold as many mines as possible and the cells that only
+    // belong to `a` hold as few as possible), then that's exactly how the mines are distributed. Without anti-mines,
+    // the cells that only belong to `a` must be safe. This includes the classic subset reasoning as a special case.
+    for (a_index, a) in constraints.iter().enumerate() {
+        let overlapping_indices = a
+            .cells
+            .iter()
+            .flat_map(|cell_position| constraints_by_cell[cell_position].iter().copied())
+            .filter(|b_index| *b_index != a_index)
+            .collect::<BTreeSet<usize>>();
+
+        for b in overlapping_indices
+            .into_iter()
+            .map(|b_index| &constraints[b_index])
+        {
+            let only_a = difference(&a.cells, &b.cells);
+            let only_b = difference(&b.cells, &a.cells);
+
+            if b.mines_amount - a.mines_amount
+                == max_mines_per_cell * only_b.len() as i32
+                    - min_mines_per_cell * only_a.len() as i32
+            {
+                for cell_position in only_b {
+                    resolution.insert(cell_position, max_mines_per_cell);
+                }
+                for cell_position in only_a {
+                    resolution.insert(cell_position, min_mines_per_cell);
+                }
+            }
+        }
+    }
+
+    resolution
+}
+
+/// Returns the cells that belong to `a`, but not to `b`. Both slices must be sorted.
+fn difference(a: &[Position], b: &[Position]) -> Vec<Position> {
+    a.iter()
+        .filter(|cell_position| b.binary_search(cell_position).is_err())
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::deduce;
+    use crate::{
+        field::{position::Position, Field},
+        rules::Rules,
+    };
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn deduce_finds_the_cells_around_a_satisfied_number() {
+        let mut field = Field::from_mine_positions(
+            3,
+            4,
+            &[Position::new(0, 3), Position::new(2, 3)],
+            Rules::default(),
+        )
+        .unwrap();
+        // Opens the zero-cells of the left part of the field along with the numbers next to them.
+        field.open_cell(Position::new(1, 0));
+
+        let deductions = deduce(&field);
+        assert_eq!(deductions.safe, BTreeSet::from([Position::new(1, 3)]));
+        assert_eq!(
+            deductions.mined,
+            BTreeSet::from([Position::new(0, 3), Position::new(2, 3)])
+        );
+    }
+
+    #[test]
+    fn deduce_applies_the_pairwise_reasoning() {
+        // The classic 1-2-1 pattern: the mines are under the 1s, and the cell under the 2 is safe.
+        //
+        // 1 2 1
+        // * . *
+        let mut field = Field::from_mine_positions(
+            2,
+            3,
+            &[Position::new(1, 0), Position::new(1, 2)],
+            Rules::default(),
+        )
+        .unwrap();
+        field.open_cell(Position::new(0, 0));
+        field.open_cell(Position::new(0, 1));
+        field.open_cell(Position::new(0, 2));
+
+        let deductions = deduce(&field);
+        assert_eq!(deductions.safe, BTreeSet::from([Position::new(1, 1)]));
+        assert_eq!(
+            deductions.mined,
+            BTreeSet::from([Position::new(1, 0), Position::new(1, 2)])
+        );
+    }
+
+    #[test]
+    fn deduce_accounts_for_the_total_number_of_mines() {
+        // The 1 tells that one of its three neighbours is mined, but since there's only one mine in total, the cells
+        // of the far right column must be safe.
+        //
+        // 1 . .
+        // . . .
+        let mut field =
+            Field::from_mine_positions(2, 3, &[Position::new(1, 1)], Rules::default()).unwrap();
+        field.open_cell(Position::new(0, 0));
+
+        let deductions = deduce(&field);
+        assert!(deductions.safe.contains(&Position::new(0, 2)));
+        assert!(deductions.safe.contains(&Position::new(1, 2)));
+    }
+
+    #[test]
+    fn deduce_does_not_peek_at_the_closed_cells() {
+        // Two fields which look exactly the same to the player, but have the mines in different places.
+        let mut field_a =
+            Field::from_mine_positions(2, 2, &[Position::new(1, 0)], Rules::default()).unwrap();
+        let mut field_b =
+            Field::from_mine_positions(2, 2, &[Position::new(1, 1)], Rules::default()).unwrap();
+        field_a.open_cell(Position::new(0, 0));
+        field_b.open_cell(Position::new(0, 0));
+        field_a.open_cell(Position::new(0, 1));
+        field_b.open_cell(Position::new(0, 1));
+
+        let deductions = deduce(&field_a);
+        assert_eq!(deductions, deduce(&field_b));
+        assert!(deductions.safe.is_empty() && deductions.mined.is_empty());
+    }
+
+    #[test]
+    fn deduce_does_not_trust_the_flags() {
+        let mut field =
+            Field::from_mine_positions(2, 2, &[Position::new(1, 0)], Rules::default()).unwrap();
+        field.open_cell(Position::new(0, 0));
+        field.open_cell(Position::new(0, 1));
+        // A wrong flag must not lead to the conclusion that the other cell is safe.
+        field.toggle_cell_flag(Position::new(1, 1));
+
+        let deductions = deduce(&field);
+        assert!(deductions.safe.is_empty() && deductions.mined.is_empty());
+    }
+
+    #[test]
+    fn deduce_finds_the_exact_numbers_of_mines_in_the_cells() {
+        let rules = Rules {
+            max_mines_per_cell: 2,
+            ..Default::default()
+        };
+
+        // The number next to the only closed cell tells how many mines there are in it, be it one or two.
+        for mines_positions in [&[Position::new(0, 3)][..], &[Position::new(0, 3); 2]] {
+            let mut field = Field::from_mine_positions(1, 4, mines_positions, rules).unwrap();
+            field.open_cell(Position::new(0, 0));
+
+            let deductions = deduce(&field);
+            assert_eq!(deductions.mined, BTreeSet::from([Position::new(0, 3)]));
+        }
+    }
+
+    #[test]
+    fn deduce_accounts_for_several_mines_per_cell() {
+        // With a single mine per cell, both of the closed cells would be mined, but now the mines might as well be in
+        // just one of them.
+        //
+        // 2 2
+        // . .
+        let rules = Rules {
+            max_mines_per_cell: 2,
+            ..Default::default()
+        };
+        let mut field =
+            Field::from_mine_positions(2, 2, &[Position::new(1, 0), Position::new(1, 1)], rules)
+                .unwrap();
+        field.open_cell(Position::new(0, 0));
+        field.open_cell(Position::new(0, 1));
+
+        let deductions = deduce(&field);
+        assert!(deductions.safe.is_empty() && deductions.mined.is_empty());
+    }
+
+    #[test]
+    fn deduce_finds_the_anti_mines() {
+        // The -2 can only be explained by two anti-mines. Once both of them are found, the 1 and the -1 (the anti-mines
+        // and the mine around it cancel out) tell that the bottom-left cell is safe.
+        //
+        // .  -2  0
+        // .  -1  1
+        // .   .  .
+        let mut field = Field::from_mine_and_anti_mine_positions(
+            3,
+            3,
+            &[Position::new(2, 2)],
+            &[Position::new(0, 0), Position::new(1, 0)],
+            Rules::default(),
+        )
+        .unwrap();
+        field.open_cell(Position::new(0, 2));
+
+        let deductions = deduce(&field);
+        assert_eq!(deductions.safe, BTreeSet::from([Position::new(2, 0)]));
+        assert_eq!(
+            deductions.mined,
+            BTreeSet::from([Position::new(0, 0), Position::new(1, 0)])
+        );
+    }
+}
@@ -0,0 +1,1019 @@
+//! The mine probabilities calculator.
+//!
+//! Apart from the cells that are provably safe or mined, every closed cell has a certain probability to be mined given
+//! everything the player can see. The probabilities are calculated exactly: all the distributions of the mines among
+//! the cells next to the open numbers are enumerated (separately for each group of cells that affect each other), and
+//! each of them is weighted with the number of ways to put the rest of the mines into the other closed cells.
+//!
+//! If the rules allow for several mines per cell, the probability is the one of holding at least one mine. A cell that
+//! might hold up to `K` mines is treated as `K` slots, each of which is equally likely to get a mine, which is exactly
+//! how the mines are distributed randomly.
+//!
+//! If the rules require anti-mines, the probability is the one of holding either a mine or an anti-mine, since both are
+//! deadly. The anti-mines are distributed first, equally likely among all the cells, and the mines go into the rest.
+//!
+//! The groups of cells with too many distributions to go through in a reasonable time are not enumerated. Their numbers
+//! are disregarded instead, so their cells get the same probability as the cells not next to any number.
+
+use super::{collect_number_constraints, resolve, Constraint};
+use crate::field::{position::Position, Field};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+/// The maximum number of steps (i.e., of the decisions upon a cell) the enumeration of the distributions of a single
+/// group of cells might take before giving up on the group.
+const MAX_ENUMERATION_STEPS_AMOUNT: u32 = 1 << 20;
+
+/// The probabilities of the cells of a field to be mined.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbabilityGrid {
+    /// A 2D vector, where the top level represents rows, and the nested vector of each row represents the cells'
+    /// probabilities. [`None`] is used for the open cells.
+    grid: Vec<Vec<Option<f64>>>,
+}
+
+impl ProbabilityGrid {
+    /// Returns the probability of the cell with the given position to be mined, from `0.0` (provably safe) to `1.0`
+    /// (provably mined), or [`None`] if the cell is open or there's no cell at the given position.
+    pub fn get(&self, position: Position) -> Option<f64> {
+        self.grid
+            .get(position.row_index as usize)
+            .and_then(|row| row.get(position.column_index as usize))
+            .copied()
+            .flatten()
+    }
+
+    /// Returns the position of the closed cell which is the least likely to be mined along with its probability, or
+    /// [`None`] if there are no closed cells. In case of a tie, the topmost (and then the leftmost) cell is preferred.
+    pub fn get_safest_cell(&self) -> Option<(Position, f64)> {
+        self.grid
+            .iter()
+            .enumerate()
+            .flat_map(|(row_index, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter_map(move |(column_index, probability)| {
+                        probability.map(|probability| {
+                            (
+                                Position::new(row_index as u16, column_index as u16),
+                                probability,
+                            )
+                        })
+                    })
+            })
+            .fold(
+                None,
+                |safest_cell, (position, probability)| match safest_cell {
+                    Some((_, safest_probability)) if safest_probability <= probability => {
+                        safest_cell
+                    }
+                    _ => Some((position, probability)),
+                },
+            )
+    }
+}
+
+/// The numbers of the ways to distribute the mines by the amounts of anti-mines and of mines in the distributions (i.e.,
+/// `weights[a][k]` is the number of the distributions with `a` anti-mines and `k` mines). Without anti-mines, there's
+/// only one row.
+type Weights = Vec<Vec<f64>>;
+
+/// A group of the cells next to the open numbers which affect each other's probabilities, along with the number of the
+/// ways the mines can be distributed among them.
+///
+/// The numbers might be scaled (all by the same factor) to keep them within the range of [`f64`].
+struct Component {
+    /// The positions of the cells of the group.
+    cells: Vec<Position>,
+    /// The number of the valid distributions by the amounts of anti-mines and mines in them. With several mines per
+    /// cell, each distribution is counted as many times as the number of ways to choose the slots for the mines.
+    weights: Weights,
+    /// The number of the valid distributions where a cell is mined (or anti-mined), by the amounts of anti-mines and
+    /// mines in them and by the index of the cell (i.e., `cell_weights[a][k][i]` is the number of the distributions with
+    /// `a` anti-mines and `k` mines having `cells[i]` mined).
+    cell_weights: Vec<Vec<Vec<f64>>>,
+}
+
+/// Calculates the probability of each closed cell of the field to be mined.
+///
+/// Just like the rest of the solver, the calculation only relies on the information visible to the player, including
+/// the total number of mines. The number of the distributions grows exponentially with the size of a group of the
+/// unresolved cells next to the open numbers, so the probabilities of the cells of the groups too large to enumerate
+/// are estimated as if they weren't next to any number.
+pub fn calculate_mine_probabilities(field: &Field) -> ProbabilityGrid {
+    let resolution = resolve(field);
+    let constraints = collect_number_constraints(field, &resolution);
+    let (rows_amount, columns_amount, _) = field.get_size();
+    let rules = field.get_rules();
+    let max_mines_per_cell = rules.max_mines_per_cell as usize;
+    let with_anti_mines = rules.anti_mines_amount > 0;
+
+    let mut grid = vec![vec![None; columns_amount as usize]; rows_amount as usize];
+    let mut known_mines_amount = 0;
+    let mut known_anti_mines_amount = 0;
+    let mut unresolved_cells = Vec::new();
+
+    let mut count_known = |mines_amount: i32| {
+        if mines_amount < 0 {
+            known_anti_mines_amount += 1;
+        } else {
+            known_mines_amount += mines_amount as usize;
+        }
+    };
+
+    for position in (0..rows_amount).flat_map(|row_index| {
+        (0..columns_amount).map(move |column_index| Position::new(row_index, column_index))
+    }) {
+        let Some(cell) = field.get_cell(position) else {
+            continue;
+        };
+
+        let probability = if cell.is_open() {
+            // an open mine is visible to the player (e.g., the one that has been hit)
+            count_known(cell.get_signed_mines_amount() as i32);
+
+            None
+        } else if resolution.safe.contains(&position) {
+            Some(0.0)
+        } else if let Some(mines_amount) = resolution.mines_amounts.get(&position) {
+            count_known(*mines_amount);
+
+            Some(1.0)
+        } else {
+            unresolved_cells.push(position);
+            continue;
+        };
+
+        grid[position.row_index as usize][position.column_index as usize] = probability;
+    }
+
+    let remaining_mines_amount =
+        (field.get_mines_amount() as usize).saturating_sub(known_mines_amount);
+    let remaining_anti_mines_amount =
+        (field.get_anti_mines_amount() as usize).saturating_sub(known_anti_mines_amount);
+
+    let components = split_into_components(&constraints)
+        .into_iter()
+        .filter_map(|group| {
+            enumerate_distributions(
+                group,
+                remaining_anti_mines_amount,
+                remaining_mines_amount,
+                max_mines_per_cell,
+                with_anti_mines,
+            )
+        })
+        .collect::<Vec<Component>>();
+
+    // The cells which are not next to any number (or whose group hasn't been enumerated). All of them are equally likely
+    // to be mined.
+    let frontier_cells = components
+        .iter()
+        .flat_map(|component| component.cells.iter().copied())
+        .collect::<BTreeSet<Position>>();
+    let interior_cells = unresolved_cells
+        .into_iter()
+        .filter(|position| !frontier_cells.contains(position))
+        .collect::<Vec<Position>>();
+
+    // `interior_weights[a][k]` is the number of ways to put the rest of the anti-mines and mines into the interior
+    // cells, given that there are `a` anti-mines and `k` mines next to the numbers.
+    let interior_weights = calculate_interior_weights(
+        interior_cells.len(),
+        remaining_anti_mines_amount,
+        remaining_mines_amount,
+        if with_anti_mines {
+            frontier_cells.len()
+        } else {
+            0
+        },
+        frontier_cells.len() * max_mines_per_cell,
+        max_mines_per_cell,
+    );
+    let get_interior_weight = |anti_mines_amount: usize, mines_amount: usize| {
+        interior_weights
+            .get(anti_mines_amount)
+            .and_then(|row| row.get(mines_amount))
+            .copied()
+            .unwrap_or(0.0)
+    };
+
+    // The distributions of all the components together by the amounts of anti-mines and mines: `prefixes[i]` is the one
+    // for the first `i` components and `suffixes[i]` is the one for all the components starting from the `i`-th one.
+    let mut prefixes = vec![vec![vec![1.0]]];
+    for component in components.iter() {
+        prefixes.push(convolve(prefixes.last().unwrap(), &component.weights));
+    }
+
+    let mut suffixes = vec![vec![vec![1.0]]];
+    for component in components.iter().rev() {
+        suffixes.push(convolve(suffixes.last().unwrap(), &component.weights));
+    }
+    suffixes.reverse();
+
+    for (index, component) in components.iter().enumerate() {
+        // The distributions of all the other components.
+        let others_weights = convolve(&prefixes[index], &suffixes[index + 1]);
+
+        let mut total_weight = 0.0;
+        let mut cells_total_weights = vec![0.0; component.cells.len()];
+
+        for (anti_mines_amount, row) in component.weights.iter().enumerate() {
+            for (mines_amount, weight) in row.iter().enumerate() {
+                if *weight == 0.0 {
+                    continue;
+                }
+
+                // The number of ways to complete a distribution of the component with the given amounts of anti-mines
+                // and mines.
+                let factor = others_weights
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(others_anti_mines_amount, others_row)| {
+                        others_row.iter().enumerate().map(
+                            move |(others_mines_amount, others_weight)| {
+                                others_weight
+                                    * get_interior_weight(
+                                        anti_mines_amount + others_anti_mines_amount,
+                                        mines_amount + others_mines_amount,
+                                    )
+                            },
+                        )
+                    })
+                    .sum::<f64>();
+
+                total_weight += weight * factor;
+                cells_total_weights
+                    .iter_mut()
+                    .zip(component.cell_weights[anti_mines_amount][mines_amount].iter())
+                    .for_each(|(cell_total_weight, cell_weight)| {
+                        *cell_total_weight += cell_weight * factor
+                    });
+            }
+        }
+
+        for (position, cell_total_weight) in component.cells.iter().zip(cells_total_weights) {
+            grid[position.row_index as usize][position.column_index as usize] =
+                Some(calculate_ratio(cell_total_weight, total_weight));
+        }
+    }
+
+    if !interior_cells.is_empty() {
+        let mut total_weight = 0.0;
+        let mut mines_weight = 0.0;
+
+        for (frontier_anti_mines_amount, row) in prefixes[components.len()].iter().enumerate() {
+            for (frontier_mines_amount, weight) in row.iter().enumerate() {
+                let weight =
+                    weight * get_interior_weight(frontier_anti_mines_amount, frontier_mines_amount);
+
+                if weight == 0.0 {
+                    continue;
+                }
+
+                total_weight += weight;
+                mines_weight += weight
+                    * calculate_interior_mine_probability(
+                        interior_cells.len(),
+                        remaining_anti_mines_amount - frontier_anti_mines_amount,
+                        remaining_mines_amount - frontier_mines_amount,
+                        max_mines_per_cell,
+                    );
+            }
+        }
+
+        let probability = calculate_ratio(mines_weight, total_weight);
+
+        for position in interior_cells {
+            grid[position.row_index as usize][position.column_index as usize] = Some(probability);
+        }
+    }
+
+    ProbabilityGrid { grid }
+}
+
+/// The constraints which share cells with each other (directly or through other constraints).
+struct ConstraintGroup<'a> {
+    /// The cells of all the constraints, ordered so that the neighbouring cells go next to each other.
+    cells: Vec<Position>,
+    /// The constraints themselves.
+    constraints: Vec<&'a Constraint>,
+}
+
+/// Splits the constraints into the groups that don't share any cells.
+fn split_into_components(constraints: &[Constraint]) -> Vec<ConstraintGroup<'_>> {
+    let mut constraints_by_cell: HashMap<Position, Vec<usize>> = HashMap::new();
+    for (index, constraint) in constraints.iter().enumerate() {
+        for cell_position in constraint.cells.iter() {
+            constraints_by_cell
+                .entry(*cell_position)
+                .or_default()
+                .push(index);
+        }
+    }
+
+    let mut visited_constraints = vec![false; constraints.len()];
+    let mut visited_cells = BTreeSet::new();
+    let mut components = Vec::new();
+
+    for starting_index in 0..constraints.len() {
+        if visited_constraints[starting_index] {
+            continue;
+        }
+
+        // Walk through the constraints breadth-first, collecting the cells in the order they're reached.
+        let mut cells = Vec::new();
+        let mut component_constraints = Vec::new();
+        let mut queue = VecDeque::from([starting_index]);
+        visited_constraints[starting_index] = true;
+
+        while let Some(index) = queue.pop_front() {
+            component_constraints.push(&constraints[index]);
+
+            for cell_position in constraints[index].cells.iter() {
+                if visited_cells.insert(*cell_position) {
+                    cells.push(*cell_position);
+
+                    for adjacent_index in constraints_by_cell[cell_position].iter() {
+                        if !visited_constraints[*adjacent_index] {
+                            visited_constraints[*adjacent_index] = true;
+                            queue.push_back(*adjacent_index);
+                        }
+                    }
+                }
+            }
+        }
+
+        components.push(ConstraintGroup {
+            cells,
+            constraints: component_constraints,
+        });
+    }
+
+    components
+}
+
+/// The state of a constraint while enumerating the distributions.
+#[derive(Clone, Copy)]
+struct ConstraintState {
+    /// The number of mines yet to be put into the cells of the constraint, where an anti-mine counts as -1.
+    mines_left: i32,
+    /// The number of the cells of the constraint that haven't been decided upon yet.
+    cells_left: i32,
+}
+
+/// Enumerates all the distributions of the mines among the cells of the group (each holding up to `max_mines_per_cell`
+/// of them or, if `with_anti_mines` is set, an anti-mine) that satisfy its constraints and have no more than
+/// `max_anti_mines_amount` anti-mines and `max_mines_amount` mines.
+///
+/// Returns [`None`] if there are too many distributions to go through (see [`MAX_ENUMERATION_STEPS_AMOUNT`]).
+fn enumerate_distributions(
+    group: ConstraintGroup,
+    max_anti_mines_amount: usize,
+    max_mines_amount: usize,
+    max_mines_per_cell: usize,
+    with_anti_mines: bool,
+) -> Option<Component> {
+    let ConstraintGroup { cells, constraints } = group;
+
+    let index_by_cell = cells
+        .iter()
+        .enumerate()
+        .map(|(index, position)| (*position, index))
+        .collect::<HashMap<Position, usize>>();
+
+    let mut constraints_by_cell = vec![Vec::new(); cells.len()];
+    for (constraint_index, constraint) in constraints.iter().enumerate() {
+        for cell_position in constraint.cells.iter() {
+            constraints_by_cell[index_by_cell[cell_position]].push(constraint_index);
+        }
+    }
+
+    let max_anti_mines_amount = if with_anti_mines {
+        max_anti_mines_amount.min(cells.len())
+    } else {
+        0
+    };
+    let max_mines_amount = max_mines_amount.min(cells.len() * max_mines_per_cell);
+
+    let mut enumeration = Enumeration {
+        constraints_by_cell,
+        states: constraints
+            .iter()
+            .map(|constraint| ConstraintState {
+                mines_left: constraint.mines_amount,
+                cells_left: constraint.cells.len() as i32,
+            })
+            .collect(),
+        mines_amounts: vec![0; cells.len()],
+        max_anti_mines_amount,
+        max_mines_amount,
+        min_mines_per_cell: if with_anti_mines { -1 } else { 0 },
+        slots_weights: (0..=max_mines_per_cell)
+            .map(|mines_amount| calculate_binomial(max_mines_per_cell, mines_amount))
+            .collect(),
+        weights: vec![vec![0.0; max_mines_amount + 1]; max_anti_mines_amount + 1],
+        cell_weights: vec![
+            vec![vec![0.0; cells.len()]; max_mines_amount + 1];
+            max_anti_mines_amount + 1
+        ],
+        steps_left: MAX_ENUMERATION_STEPS_AMOUNT,
+    };
+    enumeration.visit(0, 0, 0, 1.0);
+
+    if enumeration.steps_left == 0 {
+        return None;
+    }
+
+    // Scale the numbers down, since they might get really big.
+    let Enumeration {
+        mut weights,
+        mut cell_weights,
+        ..
+    } = enumeration;
+    let max_weight = weights.iter().flatten().copied().fold(0.0, f64::max);
+
+    if max_weight > 0.0 {
+        weights
+            .iter_mut()
+            .flatten()
+            .for_each(|weight| *weight /= max_weight);
+        cell_weights
+            .iter_mut()
+            .flatten()
+            .flatten()
+            .for_each(|weight| *weight /= max_weight);
+    }
+
+    Some(Component {
+        cells,
+        weights,
+        cell_weights,
+    })
+}
+
+/// The state of the backtracking enumeration of the distributions of the mines among a group of cells.
+struct Enumeration {
+    /// The indices of the constraints each of the cells is a part of.
+    constraints_by_cell: Vec<Vec<usize>>,
+    /// The states of the constraints.
+    states: Vec<ConstraintState>,
+    /// The number of mines in each of the already decided cells, where an anti-mine is -1.
+    mines_amounts: Vec<i32>,
+    /// The maximum number of anti-mines a distribution might have.
+    max_anti_mines_amount: usize,
+    /// The maximum number of mines a distribution might have.
+    max_mines_amount: usize,
+    /// The least number of mines a cell might hold: -1 (an anti-mine) if there are anti-mines, and 0 otherwise.
+    min_mines_per_cell: i32,
+    /// The number of ways to choose the slots of a cell for the given number of mines (i.e., `slots_weights[k]` is the
+    /// number of ways to put `k` mines into a cell). The length is the maximum number of mines per cell plus one.
+    slots_weights: Vec<f64>,
+    /// See [`Component::weights`].
+    weights: Weights,
+    /// See [`Component::cell_weights`].
+    cell_weights: Vec<Vec<Vec<f64>>>,
+    /// The number of steps the enumeration might still take. Once it reaches `0`, the enumeration is abandoned.
+    steps_left: u32,
+}
+
+impl Enumeration {
+    /// Decides upon the cell with the given index and all the following ones, given that `anti_mines_amount` anti-mines
+    /// and `mines_amount` mines have already been put into the preceding cells in `weight` ways.
+    fn visit(
+        &mut self,
+        cell_index: usize,
+        anti_mines_amount: usize,
+        mines_amount: usize,
+        weight: f64,
+    ) {
+        let Some(steps_left) = self.steps_left.checked_sub(1) else {
+            return;
+        };
+        self.steps_left = steps_left;
+
+        if cell_index == self.mines_amounts.len() {
+            self.weights[anti_mines_amount][mines_amount] += weight;
+            self.mines_amounts
+                .iter()
+                .zip(self.cell_weights[anti_mines_amount][mines_amount].iter_mut())
+                .filter(|(cell_mines_amount, _)| **cell_mines_amount != 0)
+                .for_each(|(_, cell_weight)| *cell_weight += weight);
+
+            return;
+        }
+
+        let max_mines_per_cell = self.slots_weights.len() as i32 - 1;
+
+        for cell_mines_amount in self.min_mines_per_cell..=max_mines_per_cell {
+            let (next_anti_mines_amount, next_mines_amount, cell_weight) = if cell_mines_amount < 0
+            {
+                (anti_mines_amount + 1, mines_amount, 1.0)
+            } else {
+                (
+                    anti_mines_amount,
+                    mines_amount + cell_mines_amount as usize,
+                    self.slots_weights[cell_mines_amount as usize],
+                )
+            };
+
+            if next_anti_mines_amount > self.max_anti_mines_amount {
+                continue;
+            } else if next_mines_amount > self.max_mines_amount {
+                break;
+            }
+
+            // Every constraint must still be satisfiable with the remaining cells.
+            let is_possible = self.constraints_by_cell[cell_index].iter().all(|index| {
+                let mines_left = self.states[*index].mines_left - cell_mines_amount;
+                let cells_left = self.states[*index].cells_left - 1;
+
+                (cells_left * self.min_mines_per_cell..=cells_left * max_mines_per_cell)
+                    .contains(&mines_left)
+            });
+
+            if !is_possible {
+                continue;
+            }
+
+            self.update_states(cell_index, cell_mines_amount, -1);
+            self.mines_amounts[cell_index] = cell_mines_amount;
+
+            self.visit(
+                cell_index + 1,
+                next_anti_mines_amount,
+                next_mines_amount,
+                weight * cell_weight,
+            );
+
+            self.mines_amounts[cell_index] = 0;
+            self.update_states(cell_index, cell_mines_amount, 1);
+        }
+    }
+
+    /// Takes the cell with the given number of mines into (`delta` being `-1`) or out of (`delta` being `1`) account
+    /// for all of its constraints.
+    fn update_states(&mut self, cell_index: usize, cell_mines_amount: i32, delta: i32) {
+        for index in self.constraints_by_cell[cell_index].iter() {
+            self.states[*index].cells_left += delta;
+            self.states[*index].mines_left += delta * cell_mines_amount;
+        }
+    }
+}
+
+/// Calculates the number of ways to put the anti-mines and the mines that are not next to the numbers into the
+/// interior cells, depending on the numbers of anti-mines (from `0` to `max_frontier_anti_mines_amount`) and of mines
+/// (from `0` to `max_frontier_mines_amount`) next to the numbers. The numbers are scaled, so that the largest one is
+/// `1.0`.
+///
+/// The anti-mines take up whole cells, while the mines are put into the slots of the rest of them.
+fn calculate_interior_weights(
+    interior_cells_amount: usize,
+    remaining_anti_mines_amount: usize,
+    remaining_mines_amount: usize,
+    max_frontier_anti_mines_amount: usize,
+    max_frontier_mines_amount: usize,
+    max_mines_per_cell: usize,
+) -> Weights {
+    // The binomial coefficients get astronomically large, so they are calculated as logarithms.
+    let slots_amount = interior_cells_amount * max_mines_per_cell;
+    let mut ln_factorials = vec![0.0; slots_amount + 1];
+    for n in 1..=slots_amount {
+        ln_factorials[n] = ln_factorials[n - 1] + (n as f64).ln();
+    }
+    let ln_binomial = |n: usize, k: usize| {
+        (k <= n).then(|| ln_factorials[n] - ln_factorials[k] - ln_factorials[n - k])
+    };
+
+    let ln_weights = (0..=max_frontier_anti_mines_amount)
+        .map(|frontier_anti_mines_amount| {
+            (0..=max_frontier_mines_amount)
+                .map(|frontier_mines_amount| {
+                    let interior_anti_mines_amount =
+                        remaining_anti_mines_amount.checked_sub(frontier_anti_mines_amount)?;
+                    let interior_mines_amount =
+                        remaining_mines_amount.checked_sub(frontier_mines_amount)?;
+
+                    let ln_anti_mines_weight =
+                        ln_binomial(interior_cells_amount, interior_anti_mines_amount)?;
+                    let ln_mines_weight = ln_binomial(
+                        (interior_cells_amount - interior_anti_mines_amount) * max_mines_per_cell,
+                        interior_mines_amount,
+                    )?;
+
+                    Some(ln_anti_mines_weight + ln_mines_weight)
+                })
+                .collect::<Vec<Option<f64>>>()
+        })
+        .collect::<Vec<Vec<Option<f64>>>>();
+
+    let max_ln_weight = ln_weights
+        .iter()
+        .flatten()
+        .flatten()
+        .copied()
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    ln_weights
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|ln_weight| {
+                    ln_weight.map_or(0.0, |ln_weight| (ln_weight - max_ln_weight).exp())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Calculates the probability of an interior cell to hold at least one mine or an anti-mine, given that there are
+/// `anti_mines_amount` anti-mines and `mines_amount` mines among the `cells_amount` interior cells, each of which might
+/// hold up to `max_mines_per_cell` mines.
+fn calculate_interior_mine_probability(
+    cells_amount: usize,
+    anti_mines_amount: usize,
+    mines_amount: usize,
+    max_mines_per_cell: usize,
+) -> f64 {
+    if anti_mines_amount >= cells_amount {
+        return 1.0;
+    }
+
+    // the share of the anti-mined cells
+    let anti_mined_probability = anti_mines_amount as f64 / cells_amount as f64;
+    let cells_amount = cells_amount - anti_mines_amount;
+    let slots_amount = cells_amount * max_mines_per_cell;
+
+    let mined_probability = if max_mines_per_cell == 1 {
+        // the share of the mined cells among the rest of them
+        mines_amount as f64 / cells_amount as f64
+    } else if mines_amount >= slots_amount {
+        1.0
+    } else {
+        // The cell stays empty if all the mines get into the slots of the other cells.
+        let other_slots_amount = slots_amount - max_mines_per_cell;
+        let empty_probability = (0..mines_amount)
+            .map(|index| {
+                other_slots_amount.saturating_sub(index) as f64 / (slots_amount - index) as f64
+            })
+            .product::<f64>();
+
+        1.0 - empty_probability
+    };
+
+    anti_mined_probability + (1.0 - anti_mined_probability) * mined_probability
+}
+
+/// Calculates the binomial coefficient: the number of ways to choose `k` items out of `n`.
+fn calculate_binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |binomial, index| {
+        binomial * (n - index) as f64 / (index + 1) as f64
+    })
+}
+
+/// Calculates the distribution of the total numbers of anti-mines and mines of two independent groups of cells from the
+/// distributions of each of them. The result is scaled, so that the largest number is `1.0`.
+fn convolve(a: &Weights, b: &Weights) -> Weights {
+    let mines_amount = |weights: &Weights| weights.first().map_or(0, |row| row.len());
+    let mut result = vec![vec![0.0; mines_amount(a) + mines_amount(b) - 1]; a.len() + b.len() - 1];
+
+    for (i, a_row) in a.iter().enumerate() {
+        for (j, b_row) in b.iter().enumerate() {
+            for (k, a_weight) in a_row.iter().enumerate() {
+                for (l, b_weight) in b_row.iter().enumerate() {
+                    result[i + j][k + l] += a_weight * b_weight;
+                }
+            }
+        }
+    }
+
+    let max_weight = result.iter().flatten().copied().fold(0.0, f64::max);
+    if max_weight > 0.0 {
+        result
+            .iter_mut()
+            .flatten()
+            .for_each(|weight| *weight /= max_weight);
+    }
+
+    result
+}
+
+/// Divides the part by the total or returns `0.0` if the total is `0.0` (which only happens for contradictory fields).
+fn calculate_ratio(part: f64, total: f64) -> f64 {
+    if total > 0.0 {
+        part / total
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{calculate_binomial, calculate_mine_probabilities};
+    use crate::{
+        field::{position::Position, Field},
+        rules::{Neighborhood, Rules},
+    };
+
+    /// Calculates the probabilities by brute force: goes through all the possible layouts of the field and only keeps
+    /// the ones that look the same to the player. With several mines per cell, each layout is weighted with the number
+    /// of ways to choose the slots of the cells for their mines.
+    fn calculate_mine_probabilities_naively(field: &Field) -> Vec<Vec<Option<f64>>> {
+        let (rows_amount, columns_amount, _) = field.get_size();
+        let max_mines_per_cell = field.get_rules().max_mines_per_cell as i32;
+        let min_mines_per_cell = if field.get_anti_mines_amount() > 0 {
+            -1
+        } else {
+            0
+        };
+        let positions = (0..rows_amount)
+            .flat_map(|row_index| {
+                (0..columns_amount).map(move |column_index| Position::new(row_index, column_index))
+            })
+            .collect::<Vec<Position>>();
+
+        let mut layouts_weight = 0.0;
+        let mut mined_weights = vec![0.0; positions.len()];
+        // The number of mines in each of the cells (-1 being an anti-mine), counting up like the digits of a number.
+        let mut layout = vec![min_mines_per_cell; positions.len()];
+        let mut layout_mines_amount = 0;
+        let mut layout_anti_mines_amount = if min_mines_per_cell < 0 {
+            positions.len()
+        } else {
+            0
+        };
+
+        loop {
+            let get_mines_amount = |Position {
+                                        row_index,
+                                        column_index,
+                                    }: Position| {
+                layout[row_index as usize * columns_amount as usize + column_index as usize]
+            };
+
+            let looks_the_same = layout_mines_amount == field.get_mines_amount()
+                && layout_anti_mines_amount as u32 == field.get_anti_mines_amount()
+                && positions.iter().all(|position| {
+                    let cell = field.get_cell(*position).unwrap();
+
+                    !cell.is_open()
+                        || cell.get_signed_mines_amount() as i32 == get_mines_amount(*position)
+                            && cell.get_mines_around_amount().is_none_or(|amount| {
+                                field
+                                    .get_adjacent_positions(*position)
+                                    .into_iter()
+                                    .map(get_mines_amount)
+                                    .sum::<i32>()
+                                    == amount as i32
+                            })
+                });
+
+            if looks_the_same {
+                let weight = layout
+                    .iter()
+                    .filter(|mines_amount| **mines_amount > 0)
+                    .map(|mines_amount| {
+                        calculate_binomial(max_mines_per_cell as usize, *mines_amount as usize)
+                    })
+                    .product::<f64>();
+
+                layouts_weight += weight;
+                layout
+                    .iter()
+                    .zip(mined_weights.iter_mut())
+                    .filter(|(mines_amount, _)| **mines_amount != 0)
+                    .for_each(|(_, mined_weight)| *mined_weight += weight);
+            }
+
+            // Move on to the next layout.
+            let Some(index) = layout
+                .iter()
+                .position(|mines_amount| *mines_amount < max_mines_per_cell)
+            else {
+                break;
+            };
+
+            layout[..index].fill(min_mines_per_cell);
+            layout_mines_amount -= index as u32 * max_mines_per_cell as u32;
+            if min_mines_per_cell < 0 {
+                layout_anti_mines_amount += index;
+            }
+
+            if layout[index] < 0 {
+                layout_anti_mines_amount -= 1;
+            } else {
+                layout_mines_amount += 1;
+            }
+            layout[index] += 1;
+        }
+
+        positions
+            .iter()
+            .enumerate()
+            .map(|(index, position)| {
+                (!field.get_cell(*position).unwrap().is_open())
+                    .then(|| mined_weights[index] / layouts_weight)
+            })
+            .collect::<Vec<Option<f64>>>()
+            .chunks(columns_amount as usize)
+            .map(|row| row.to_vec())
+            .collect()
+    }
+
+    fn assert_probabilities_match(field: &Field) {
+        let probabilities = calculate_mine_probabilities(field);
+        let expected_probabilities = calculate_mine_probabilities_naively(field);
+
+        for (row_index, row) in expected_probabilities.iter().enumerate() {
+            for (column_index, expected_probability) in row.iter().enumerate() {
+                let probability =
+                    probabilities.get(Position::new(row_index as u16, column_index as u16));
+
+                match (probability, expected_probability) {
+                    (Some(a), Some(b)) => assert!((a - b).abs() < 1e-9, "{a} != {b}"),
+                    (a, b) => assert_eq!(a, *b),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn all_the_cells_are_equally_likely_to_be_mined_before_the_first_move() {
+        let field = Field::new(4, 5, 6, Rules::default(), None).unwrap();
+        let probabilities = calculate_mine_probabilities(&field);
+
+        assert_eq!(probabilities.get(Position::new(0, 0)), Some(0.3));
+        assert_eq!(probabilities.get(Position::new(3, 4)), Some(0.3));
+        assert_eq!(probabilities.get(Position::new(4, 0)), None);
+    }
+
+    #[test]
+    fn the_cells_of_the_groups_too_large_to_enumerate_get_the_average_probability() {
+        // The number in the middle sees 12 mines among its 24 closed neighbours, which have way too many distributions
+        // to go through, so it's disregarded.
+        let rules = Rules {
+            neighborhood: Neighborhood::Extended,
+            ..Default::default()
+        };
+        let mines_positions = (1..=5)
+            .flat_map(|row_index| {
+                (1..=5).map(move |column_index| Position::new(row_index, column_index))
+            })
+            .take(12)
+            .chain([
+                Position::new(0, 0),
+                Position::new(0, 6),
+                Position::new(6, 6),
+            ])
+            .collect::<Vec<Position>>();
+        let mut field = Field::from_mine_positions(7, 7, &mines_positions, rules).unwrap();
+        field.open_cell(Position::new(3, 3));
+
+        let probabilities = calculate_mine_probabilities(&field);
+        for position in [
+            Position::new(1, 1),
+            Position::new(4, 5),
+            Position::new(6, 0),
+        ] {
+            assert!((probabilities.get(position).unwrap() - 15.0 / 48.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn the_probabilities_account_for_the_total_number_of_mines() {
+        // The 1 has one mine among its three neighbours, and the other mine is somewhere among the four other cells.
+        //
+        // 1 . . .
+        // . . . .
+        let mut field = Field::from_mine_positions(
+            2,
+            4,
+            &[Position::new(1, 1), Position::new(1, 3)],
+            Rules::default(),
+        )
+        .unwrap();
+        field.open_cell(Position::new(0, 0));
+
+        let probabilities = calculate_mine_probabilities(&field);
+        assert_eq!(probabilities.get(Position::new(0, 0)), None);
+        assert!((probabilities.get(Position::new(0, 1)).unwrap() - 1.0 / 3.0).abs() < 1e-9);
+        assert!((probabilities.get(Position::new(1, 3)).unwrap() - 0.25).abs() < 1e-9);
+        assert_probabilities_match(&field);
+    }
+
+    #[test]
+    fn the_probabilities_match_the_naive_calculation() {
+        let layouts: [&[Position]; 3] = [
+            &[
+                Position::new(0, 3),
+                Position::new(2, 1),
+                Position::new(3, 3),
+                Position::new(3, 4),
+            ],
+            &[
+                Position::new(1, 1),
+                Position::new(1, 2),
+                Position::new(3, 0),
+            ],
+            &[
+                Position::new(0, 0),
+                Position::new(2, 2),
+                Position::new(2, 4),
+                Position::new(3, 1),
+                Position::new(3, 2),
+            ],
+        ];
+
+        for mines_positions in layouts {
+            let mut field =
+                Field::from_mine_positions(4, 5, mines_positions, Rules::default()).unwrap();
+
+            // Open the safe cells one by one, checking the probabilities at each step.
+            for position in [
+                Position::new(0, 0),
+                Position::new(1, 4),
+                Position::new(0, 2),
+                Position::new(2, 0),
+                Position::new(3, 3),
+            ] {
+                if !field.get_cell(position).unwrap().is_mined() {
+                    field.open_cell(position);
+                    assert_probabilities_match(&field);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn the_probabilities_account_for_several_mines_per_cell() {
+        let rules = Rules {
+            max_mines_per_cell: 2,
+            ..Default::default()
+        };
+        let layouts: [&[Position]; 2] = [
+            &[
+                Position::new(0, 2),
+                Position::new(0, 2),
+                Position::new(2, 1),
+            ],
+            &[
+                Position::new(1, 2),
+                Position::new(2, 0),
+                Position::new(2, 0),
+                Position::new(2, 2),
+            ],
+        ];
+
+        for mines_positions in layouts {
+            let mut field = Field::from_mine_positions(3, 4, mines_positions, rules).unwrap();
+            assert_probabilities_match(&field);
+
+            for position in [
+                Position::new(0, 0),
+                Position::new(1, 3),
+                Position::new(2, 3),
+            ] {
+                if !field.get_cell(position).unwrap().is_mined() {
+                    field.open_cell(position);
+                    assert_probabilities_match(&field);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn the_probabilities_account_for_anti_mines() {
+        let layouts: [(&[Position], &[Position]); 2] = [
+            (
+                &[Position::new(0, 2), Position::new(2, 1)],
+                &[Position::new(1, 3)],
+            ),
+            (
+                &[Position::new(1, 1), Position::new(2, 3)],
+                &[Position::new(0, 3), Position::new(2, 0)],
+            ),
+        ];
+
+        for (mines_positions, anti_mines_positions) in layouts {
+            let mut field = Field::from_mine_and_anti_mine_positions(
+                3,
+                4,
+                mines_positions,
+                anti_mines_positions,
+                Rules::default(),
+            )
+            .unwrap();
+            assert_probabilities_match(&field);
+
+            for position in [
+                Position::new(0, 0),
+                Position::new(1, 2),
+                Position::new(2, 2),
+            ] {
+                if !field.get_cell(position).unwrap().is_mined() {
+                    field.open_cell(position);
+                    assert_probabilities_match(&field);
+                }
+            }
+        }
+    }
+}
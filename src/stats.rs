@@ -0,0 +1,106 @@
+//! The efficiency statistics of a game.
+
+use crate::field::metrics::FieldMetrics;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The numbers of the actions of each kind taken by the player.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClicksAmounts {
+    /// The number of the requests to open a single cell.
+    pub left_clicks_amount: u32,
+    /// The number of the requests to open (or to flag) the cells surrounding a number (also known as chords).
+    pub chords_amount: u32,
+    /// The number of the requests to flag or unflag a cell.
+    pub flags_amount: u32,
+    /// The number of the actions of any of the kinds above which haven't changed anything.
+    pub wasted_clicks_amount: u32,
+}
+
+/// The statistics of a game (see [`crate::Minesweeper::get_stats`]).
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct GameStats {
+    /// The numbers of the actions taken.
+    pub clicks: ClicksAmounts,
+    /// The difficulty metrics of the field, including the part of it that's been solved.
+    pub metrics: FieldMetrics,
+    /// The time the game has been on for.
+    pub time: Duration,
+}
+
+impl GameStats {
+    /// Returns the total number of the actions taken (the left clicks, the chords and the flags).
+    pub fn get_clicks_amount(&self) -> u32 {
+        self.clicks.left_clicks_amount + self.clicks.chords_amount + self.clicks.flags_amount
+    }
+
+    /// Returns the solving speed: the solved 3BV per second.
+    pub fn get_three_bv_per_second(&self) -> f64 {
+        let seconds = self.time.as_secs_f64();
+
+        if seconds > 0.0 {
+            self.metrics.solved_three_bv as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the index of efficiency (IOE): the solved 3BV per action taken. The values above `1.0` mean that the
+    /// player's been more efficient than the bare minimum of left clicks (e.g., thanks to chording).
+    pub fn get_ioe(&self) -> f64 {
+        match self.get_clicks_amount() {
+            0 => 0.0,
+            clicks_amount => self.metrics.solved_three_bv as f64 / clicks_amount as f64,
+        }
+    }
+
+    /// Returns the correctness: the share of the actions taken that have changed something, from `0.0` to `1.0`.
+    pub fn get_correctness(&self) -> f64 {
+        match self.get_clicks_amount() {
+            0 => 0.0,
+            clicks_amount => {
+                clicks_amount.saturating_sub(self.clicks.wasted_clicks_amount) as f64
+                    / clicks_amount as f64
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ClicksAmounts, GameStats};
+    use crate::field::metrics::FieldMetrics;
+    use std::time::Duration;
+
+    #[test]
+    fn the_derived_stats_are_calculated_correctly() {
+        let stats = GameStats {
+            clicks: ClicksAmounts {
+                left_clicks_amount: 10,
+                chords_amount: 4,
+                flags_amount: 6,
+                wasted_clicks_amount: 5,
+            },
+            metrics: FieldMetrics {
+                three_bv: 40,
+                solved_three_bv: 30,
+                ..Default::default()
+            },
+            time: Duration::from_secs(15),
+        };
+
+        assert_eq!(stats.get_clicks_amount(), 20);
+        assert_eq!(stats.get_three_bv_per_second(), 2.0);
+        assert_eq!(stats.get_ioe(), 1.5);
+        assert_eq!(stats.get_correctness(), 0.75);
+    }
+
+    #[test]
+    fn the_derived_stats_are_zero_for_a_game_without_actions() {
+        let stats = GameStats::default();
+
+        assert_eq!(stats.get_three_bv_per_second(), 0.0);
+        assert_eq!(stats.get_ioe(), 0.0);
+        assert_eq!(stats.get_correctness(), 0.0);
+    }
+}
@@ -3,6 +3,7 @@
 //! Has been taken from here (https://github.com/ellisonch/rust-stopwatch) and adopted for the needs
 //! of the game.
 
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::default::Default;
 use std::time::{Duration, Instant};
 
@@ -38,6 +39,41 @@ impl Stopwatch {
 
         elapsed
     }
+
+    /// Checks whether the stopwatch is currently running.
+    pub fn is_running(&self) -> bool {
+        self.start_time.is_some()
+    }
+}
+
+/// The serializable snapshot of a stopwatch. An [`Instant`] is meaningless outside of the current process, so only the
+/// elapsed time and whether the stopwatch is running are preserved.
+#[derive(Serialize, Deserialize)]
+struct StopwatchSnapshot {
+    elapsed: Duration,
+    running: bool,
+}
+
+impl Serialize for Stopwatch {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StopwatchSnapshot {
+            elapsed: self.get_elapsed_time(),
+            running: self.is_running(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// A running stopwatch is resumed from the moment of deserialization.
+impl<'de> Deserialize<'de> for Stopwatch {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let snapshot = StopwatchSnapshot::deserialize(deserializer)?;
+
+        Ok(Stopwatch {
+            start_time: snapshot.running.then(Instant::now),
+            elapsed: snapshot.elapsed,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -119,6 +155,26 @@ mod test {
         assert_sw_near(sw, 3 * SLEEP_MS);
     }
 
+    #[test]
+    fn the_stopwatch_state_survives_serialization() {
+        let mut sw = Stopwatch::default();
+        sw.start();
+        sw.stop();
+        sw.elapsed = Duration::from_secs(42);
+
+        let deserialized: Stopwatch =
+            serde_json::from_str(&serde_json::to_string(&sw).unwrap()).unwrap();
+        assert!(!deserialized.is_running());
+        assert_eq!(deserialized.get_elapsed_time().as_secs(), 42);
+
+        sw.start();
+
+        let deserialized: Stopwatch =
+            serde_json::from_str(&serde_json::to_string(&sw).unwrap()).unwrap();
+        assert!(deserialized.is_running());
+        assert_eq!(deserialized.get_elapsed_time().as_secs(), 42);
+    }
+
     // helpers
 
     fn sleep_ms(ms: i64) {